@@ -1,20 +1,28 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, Criterion};
 use scissor::{glam::*, *};
 
 fn triangulate() {
-    let config = Config { resolution: 0.05 };
+    let config = Config::new(0.05);
 
     Circle::new(2.0).fill([0.0; 4]).generate(&config, ());
 }
 
+fn delaunay() {
+    let config = Config::new(0.05);
+
+    Circle::new(2.0)
+        .fill_with(Fill::new([0.0; 4]).triangulation(Triangulation::Delaunay))
+        .generate(&config, ());
+}
+
 fn outline() {
-    let config = Config { resolution: 0.05 };
+    let config = Config::new(0.05);
 
     Circle::new(2.0).outline(0.3).generate(&config, ());
 }
 
 fn intersections() {
-    let config = Config { resolution: 0.05 };
+    let config = Config::new(0.05);
 
     Parametric::new(|x| Vec2::new(x, x.sin()), -2.0..2.0)
         .thicken(0.4, true)
@@ -23,7 +31,7 @@ fn intersections() {
 }
 
 fn combine() {
-    let config = Config { resolution: 0.05 };
+    let config = Config::new(0.05);
 
     Circle::new(2.0)
         .map(|_| {})
@@ -33,10 +41,11 @@ fn combine() {
 }
 
 fn shapes(c: &mut Criterion) {
-    c.bench_function("triangulate", |b| b.iter(|| triangulate()));
-    c.bench_function("outline", |b| b.iter(|| outline()));
-    c.bench_function("intersections", |b| b.iter(|| intersections()));
-    c.bench_function("combine", |b| b.iter(|| combine()));
+    c.bench_function("triangulate", |b| b.iter(triangulate));
+    c.bench_function("delaunay", |b| b.iter(delaunay));
+    c.bench_function("outline", |b| b.iter(outline));
+    c.bench_function("intersections", |b| b.iter(intersections));
+    c.bench_function("combine", |b| b.iter(combine));
 }
 
 criterion_group!(benches, shapes);
@@ -1,9 +1,9 @@
 use std::f32::consts::{PI, SQRT_2};
 
-use scissor::*;
+use scissor::{svg::Document, *};
 
 fn main() {
-    let cfg = Config { resolution: 0.05 };
+    let cfg = Config::new(0.05);
 
     let shape = Circle::new(1.0)
         .map(|v| {
@@ -14,33 +14,15 @@ fn main() {
         .hole(Circle::new(0.5))
         .split(|shape| shape.fill([1.0; 4]), |shape| shape);
 
-    let mesh = shape.generate(&cfg, ());
+    let (mesh, polygon) = shape.generate(&cfg, ());
 
-    println!("{:?}", mesh);
+    let mut document = Document::new();
+    document.wireframe = true;
+    document.add(&mesh);
 
-    /*
-    for mut mesh in mesh {
-        mesh.polygon.make_simple();
-        mesh.polygon.remove_intersection();
+    document.fill = [0.0; 4];
+    document.stroke = [1.0, 0.0, 0.0, 1.0];
+    document.add(&polygon);
 
-        mesh.holes[0].make_simple();
-        mesh.holes[0].remove_intersection();
-
-        print!("polygon(");
-
-        for point in mesh.polygon.points {
-            print!("({}, {}), ", point.x, point.y);
-        }
-
-        println!(")");
-
-        print!("polygon(");
-
-        for point in mesh.holes.pop().unwrap().points {
-            print!("({}, {}), ", point.x, point.y);
-        }
-
-        println!(")");
-    }
-    */
+    println!("{}", document);
 }
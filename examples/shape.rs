@@ -3,7 +3,7 @@ use std::f32::consts::{PI, SQRT_2, TAU};
 use scissor::{glam::*, *};
 
 fn main() {
-    let cfg = Config { resolution: 0.1 };
+    let cfg = Config::new(0.1);
 
     let shape = Parametric::new(|x| Vec2::new(x.sin(), -x.cos()), 0.0..TAU)
         .complete()
@@ -0,0 +1,848 @@
+use std::collections::{HashMap, HashSet};
+
+use glam::Vec2;
+
+use crate::{
+    holed_polygon::HoledPolygon,
+    polygon::{bounds, Polygon},
+    shapes::BooleanOp,
+};
+
+/// Tolerance used when snapping intersections to existing vertices.
+const EPSILON: f32 = 1e-5;
+
+/// Bitwise key of a point, intersections are copied exactly between edges so equal points
+/// will always have equal keys.
+#[inline]
+fn key(p: Vec2) -> (u32, u32) {
+    // -0.0 and 0.0 are equal but differ in their bits
+    let p = p + Vec2::ZERO;
+
+    (p.x.to_bits(), p.y.to_bits())
+}
+
+/// Directed edge with the region it bounds on the left.
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    p0: Vec2,
+    p1: Vec2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Location {
+    Inside,
+    Outside,
+    /// Shared with an edge of the other operand pointing the same way.
+    Same,
+    /// Shared with an edge of the other operand pointing the opposite way.
+    Opposite,
+}
+
+/// Collects the rings of a set of polygons, outer rings ccw and holes cw.
+fn rings(polygons: &[HoledPolygon]) -> Vec<Vec<Vec2>> {
+    let mut rings = Vec::new();
+
+    let mut push_ring = |polygon: &Polygon, ccw: bool| {
+        let mut polygon = polygon.clone();
+        polygon.clean();
+
+        if polygon.points.len() < 3 {
+            return;
+        }
+
+        if polygon.is_ccw() != ccw {
+            polygon.points.reverse();
+        }
+
+        rings.push(polygon.points);
+    };
+
+    for polygon in polygons {
+        push_ring(&polygon.polygon, true);
+
+        for hole in &polygon.holes {
+            push_ring(hole, false);
+        }
+    }
+
+    rings
+}
+
+/// Collects the edges of a set of polygons, outer rings ccw and holes cw.
+///
+/// Overlapping polygons would count twice, so they are merged first.
+fn edges(polygons: &[HoledPolygon]) -> Vec<Edge> {
+    let mut rings = rings(polygons);
+
+    if polygons.len() > 1 {
+        let slices = rings.iter().map(Vec::as_slice).collect();
+
+        rings = self::rings(&resolve(&[slices], |windings| windings[0] > 0));
+    }
+
+    let mut edges = Vec::new();
+
+    for ring in &rings {
+        for i in 0..ring.len() {
+            edges.push(Edge {
+                p0: ring[i],
+                p1: ring[(i + 1) % ring.len()],
+            });
+        }
+    }
+
+    edges
+}
+
+/// Parameter of `p` projected onto `edge`.
+#[inline]
+fn project(edge: &Edge, p: Vec2) -> f32 {
+    let d = edge.p1 - edge.p0;
+    (p - edge.p0).dot(d) / d.length_squared()
+}
+
+/// Finds all points where `a` and `b` touch, snapping to endpoints where possible.
+fn intersect(a: &Edge, b: &Edge, splits_a: &mut Vec<(f32, Vec2)>, splits_b: &mut Vec<(f32, Vec2)>) {
+    let r = a.p1 - a.p0;
+    let s = b.p1 - b.p0;
+
+    let denom = r.perp_dot(s);
+    let scale = r.length() * s.length();
+
+    if denom.abs() > EPSILON * scale {
+        let t = (b.p0 - a.p0).perp_dot(s) / denom;
+        let u = (b.p0 - a.p0).perp_dot(r) / denom;
+
+        let ea = EPSILON / r.length().max(EPSILON);
+        let eb = EPSILON / s.length().max(EPSILON);
+
+        if t < -ea || t > 1.0 + ea || u < -eb || u > 1.0 + eb {
+            return;
+        }
+
+        let a_end = t <= ea || t >= 1.0 - ea;
+        let b_end = u <= eb || u >= 1.0 - eb;
+
+        let p = if t <= ea {
+            a.p0
+        } else if t >= 1.0 - ea {
+            a.p1
+        } else if u <= eb {
+            b.p0
+        } else if u >= 1.0 - eb {
+            b.p1
+        } else {
+            a.p0 + r * t
+        };
+
+        if !a_end {
+            splits_a.push((t, p));
+        }
+
+        if !b_end {
+            splits_b.push((u, p));
+        }
+    } else {
+        // parallel, only overlapping if collinear
+        if (b.p0 - a.p0).perp_dot(r).abs() > EPSILON * r.length() {
+            return;
+        }
+
+        for &p in &[b.p0, b.p1] {
+            let t = project(a, p);
+
+            if t > 0.0 && t < 1.0 && p != a.p0 && p != a.p1 {
+                splits_a.push((t, p));
+            }
+        }
+
+        for &p in &[a.p0, a.p1] {
+            let u = project(b, p);
+
+            if u > 0.0 && u < 1.0 && p != b.p0 && p != b.p1 {
+                splits_b.push((u, p));
+            }
+        }
+    }
+}
+
+/// Splits edges at their splitting points.
+fn split(edges: &[Edge], splits: Vec<Vec<(f32, Vec2)>>) -> Vec<Edge> {
+    let mut split_edges = Vec::with_capacity(edges.len());
+
+    for (edge, mut splits) in edges.iter().zip(splits) {
+        splits.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+        let start = split_edges.len();
+        let mut p0 = edge.p0;
+
+        for (_, p) in splits.into_iter().chain(std::iter::once((1.0, edge.p1))) {
+            if p0.distance_squared(p) > EPSILON * EPSILON {
+                split_edges.push(Edge { p0, p1: p });
+                p0 = p;
+            }
+        }
+
+        // make sure the last point is exactly the end point
+        if split_edges.len() > start {
+            split_edges.last_mut().unwrap().p1 = edge.p1;
+        }
+    }
+
+    split_edges
+}
+
+/// Even-odd point in region test.
+#[inline]
+fn contains(edges: &[Edge], p: Vec2) -> bool {
+    let mut inside = false;
+
+    for edge in edges {
+        let (a, b) = (edge.p0, edge.p1);
+
+        if (a.y > p.y) != (b.y > p.y) {
+            let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+
+            if p.x < x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+fn classify(edges: &[Edge], other: &[Edge]) -> Vec<Location> {
+    let mut shared = HashMap::new();
+
+    for edge in other {
+        shared.insert((key(edge.p0), key(edge.p1)), Location::Same);
+        shared.insert((key(edge.p1), key(edge.p0)), Location::Opposite);
+    }
+
+    edges
+        .iter()
+        .map(|edge| {
+            if let Some(location) = shared.get(&(key(edge.p0), key(edge.p1))) {
+                *location
+            } else if contains(other, (edge.p0 + edge.p1) / 2.0) {
+                Location::Inside
+            } else {
+                Location::Outside
+            }
+        })
+        .collect()
+}
+
+/// Links directed edges into closed loops, always taking the sharpest left turn to keep the
+/// loops simple.
+fn link(edges: Vec<Edge>) -> Vec<Vec<Vec2>> {
+    let mut outgoing: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+
+    for (i, edge) in edges.iter().enumerate() {
+        outgoing.entry(key(edge.p0)).or_default().push(i);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut loops = Vec::new();
+
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+
+        let mut points = Vec::new();
+        let mut current = start;
+
+        loop {
+            used[current] = true;
+
+            let edge = edges[current];
+            points.push(edge.p0);
+
+            if key(edge.p1) == key(edges[start].p0) {
+                break;
+            }
+
+            let back = edge.p0 - edge.p1;
+
+            let next = outgoing.get(&key(edge.p1)).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|i| !used[*i])
+                    .map(|i| {
+                        let d = edges[i].p1 - edges[i].p0;
+
+                        // clockwise angle from the reversed incoming direction
+                        let mut angle = d.perp_dot(back).atan2(d.dot(back));
+
+                        if angle <= 0.0 {
+                            angle += std::f32::consts::TAU;
+                        }
+
+                        (i, angle)
+                    })
+                    .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                    .map(|(i, _)| i)
+            });
+
+            match next {
+                Some(next) => current = next,
+                // open chain, caused by numerical issues, discard it
+                None => {
+                    points.clear();
+                    break;
+                }
+            }
+        }
+
+        if points.len() >= 3 {
+            loops.push(points);
+        }
+    }
+
+    loops
+}
+
+#[inline]
+fn signed_area(points: &[Vec2]) -> f32 {
+    let mut area = 0.0;
+
+    for i in 0..points.len() {
+        area += points[i].perp_dot(points[(i + 1) % points.len()]);
+    }
+
+    area / 2.0
+}
+
+/// Sorts loops into outer polygons and holes.
+fn assemble(loops: Vec<Vec<Vec2>>) -> Vec<HoledPolygon> {
+    let mut outers = Vec::new();
+    let mut holes = Vec::new();
+
+    for points in loops {
+        let area = signed_area(&points);
+
+        if area > 0.0 {
+            outers.push((area, points));
+        } else if area < 0.0 {
+            holes.push(points);
+        }
+    }
+
+    // smallest first, such that holes are assigned to the innermost containing polygon
+    outers.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+    let rings: Vec<Vec<Edge>> = outers
+        .iter()
+        .map(|(_, points)| {
+            (0..points.len())
+                .map(|i| Edge {
+                    p0: points[i],
+                    p1: points[(i + 1) % points.len()],
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut polygons: Vec<HoledPolygon> = outers
+        .into_iter()
+        .map(|(_, points)| {
+            let mut polygon = Polygon::from(points);
+            polygon.is_ccw = Some(true);
+            polygon.is_simple = true;
+
+            HoledPolygon::from(polygon)
+        })
+        .collect();
+
+    for mut points in holes {
+        // a point just left of a hole edge lies in the region surrounding the hole, edges too
+        // short or too close to an outer ring for that are skipped
+        let surrounding = (0..points.len()).find_map(|i| {
+            let (p0, p1) = (points[i], points[(i + 1) % points.len()]);
+            let n = (p1 - p0).perp().normalize_or_zero();
+            let p = (p0 + p1) / 2.0 + n * EPSILON * 10.0;
+
+            if n == Vec2::ZERO {
+                return None;
+            }
+
+            rings.iter().position(|ring| contains(ring, p))
+        });
+
+        // otherwise the smallest polygon with bounds containing the hole
+        let (min, max) = bounds(&points);
+        let surrounding = surrounding.or_else(|| {
+            polygons.iter().position(|polygon| {
+                let (outer_min, outer_max) = bounds(&polygon.polygon.points);
+
+                outer_min.cmple(min).all() && outer_max.cmpge(max).all()
+            })
+        });
+
+        // a hole outside every polygon doesn't bound any region
+        if let Some(i) = surrounding {
+            points.reverse();
+
+            let mut hole = Polygon::from(points);
+            hole.is_ccw = Some(true);
+            hole.is_simple = true;
+
+            polygons[i].holes.push(hole);
+        }
+    }
+
+    polygons
+}
+
+/// Performs a boolean operation between two sets of polygons.
+///
+/// The polygons in each set may overlap each other, they are merged before the operation.
+pub(crate) fn boolean(a: &[HoledPolygon], b: &[HoledPolygon], op: BooleanOp) -> Vec<HoledPolygon> {
+    let a = edges(a);
+    let b = edges(b);
+
+    let mut splits_a = vec![Vec::new(); a.len()];
+    let mut splits_b = vec![Vec::new(); b.len()];
+
+    for (i, ea) in a.iter().enumerate() {
+        for (j, eb) in b.iter().enumerate() {
+            intersect(ea, eb, &mut splits_a[i], &mut splits_b[j]);
+        }
+    }
+
+    let a = split(&a, splits_a);
+    let b = split(&b, splits_b);
+
+    let locations_a = classify(&a, &b);
+    let locations_b = classify(&b, &a);
+
+    let reverse = |edge: &Edge| Edge {
+        p0: edge.p1,
+        p1: edge.p0,
+    };
+
+    let mut result = Vec::new();
+
+    for (edge, location) in a.iter().zip(locations_a) {
+        match (op, location) {
+            (BooleanOp::Union, Location::Outside | Location::Same)
+            | (BooleanOp::Intersection, Location::Inside | Location::Same)
+            | (BooleanOp::Difference, Location::Outside | Location::Opposite)
+            | (BooleanOp::Xor, Location::Outside) => result.push(*edge),
+            (BooleanOp::Xor, Location::Inside) => result.push(reverse(edge)),
+            _ => {}
+        }
+    }
+
+    for (edge, location) in b.iter().zip(locations_b) {
+        match (op, location) {
+            (BooleanOp::Union, Location::Outside)
+            | (BooleanOp::Intersection, Location::Inside)
+            | (BooleanOp::Xor, Location::Outside) => result.push(*edge),
+            (BooleanOp::Difference | BooleanOp::Xor, Location::Inside) => {
+                result.push(reverse(edge))
+            }
+            _ => {}
+        }
+    }
+
+    assemble(link(result))
+}
+
+/// Edges bucketed into rows by their extent along y, since only edges spanning the y
+/// coordinate of a point contribute to its winding number.
+struct Rows {
+    min: f32,
+    height: f32,
+    rows: Vec<Vec<usize>>,
+}
+
+impl Rows {
+    fn new(edges: &[Edge]) -> Self {
+        let (min, max) = edges
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), e| {
+                (min.min(e.p0.y).min(e.p1.y), max.max(e.p0.y).max(e.p1.y))
+            });
+
+        let count = (edges.len() as f32).sqrt().ceil().max(1.0) as usize;
+        let height = ((max - min) / count as f32).max(f32::MIN_POSITIVE);
+
+        let mut rows = Self {
+            min,
+            height,
+            rows: vec![Vec::new(); count],
+        };
+
+        for (i, edge) in edges.iter().enumerate() {
+            let r0 = rows.row(edge.p0.y.min(edge.p1.y));
+            let r1 = rows.row(edge.p0.y.max(edge.p1.y));
+
+            for row in &mut rows.rows[r0..=r1] {
+                row.push(i);
+            }
+        }
+
+        rows
+    }
+
+    #[inline]
+    fn row(&self, y: f32) -> usize {
+        (((y - self.min) / self.height).max(0.0) as usize).min(self.rows.len() - 1)
+    }
+}
+
+/// Winding number of each group of `edges` around `p`.
+fn winding(edges: &[Edge], groups: &[usize], rows: &Rows, p: Vec2, windings: &mut [i32]) {
+    windings.iter_mut().for_each(|w| *w = 0);
+
+    for &i in &rows.rows[rows.row(p.y)] {
+        let (edge, group) = (&edges[i], &groups[i]);
+        let (a, b) = (edge.p0, edge.p1);
+        let side = (b - a).perp_dot(p - a);
+
+        if a.y <= p.y && b.y > p.y && side > 0.0 {
+            windings[*group] += 1;
+        } else if a.y > p.y && b.y <= p.y && side < 0.0 {
+            windings[*group] -= 1;
+        }
+    }
+}
+
+/// Splits `groups` of rings at every intersection, including self intersections, and returns
+/// the region where `inside` holds for the winding numbers of the groups.
+pub(crate) fn resolve(
+    groups: &[Vec<&[Vec2]>],
+    inside: impl Fn(&[i32]) -> bool,
+) -> Vec<HoledPolygon> {
+    let mut edges = Vec::new();
+    let mut edge_groups = Vec::new();
+
+    for (group, rings) in groups.iter().enumerate() {
+        for ring in rings {
+            // edges shorter than the tolerance are dropped when splitting, which would leave a
+            // gap in the ring, so their points are merged beforehand
+            let mut points: Vec<Vec2> = Vec::with_capacity(ring.len());
+
+            for &p in ring.iter() {
+                match points.last() {
+                    Some(last) if last.distance_squared(p) <= EPSILON * EPSILON => {}
+                    _ => points.push(p),
+                }
+            }
+
+            while points.len() > 1
+                && points[0].distance_squared(points[points.len() - 1]) <= EPSILON * EPSILON
+            {
+                points.pop();
+            }
+
+            for i in 0..points.len() {
+                let (p0, p1) = (points[i], points[(i + 1) % points.len()]);
+
+                if p0 != p1 {
+                    edges.push(Edge { p0, p1 });
+                    edge_groups.push(group);
+                }
+            }
+        }
+    }
+
+    let mut splits = vec![Vec::new(); edges.len()];
+
+    // sweep along x, only edges with overlapping extents can intersect
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|&a, &b| {
+        let a = edges[a].p0.x.min(edges[a].p1.x);
+        let b = edges[b].p0.x.min(edges[b].p1.x);
+
+        a.partial_cmp(&b).unwrap()
+    });
+
+    for (n, &i) in order.iter().enumerate() {
+        let max = edges[i].p0.x.max(edges[i].p1.x);
+
+        for &j in &order[n + 1..] {
+            if edges[j].p0.x.min(edges[j].p1.x) > max + EPSILON {
+                break;
+            }
+
+            let (i, j) = (i.min(j), i.max(j));
+
+            let (a, b) = splits.split_at_mut(j);
+            intersect(&edges[i], &edges[j], &mut a[i], &mut b[0]);
+        }
+    }
+
+    let mut split_edges = Vec::new();
+    let mut split_groups = Vec::new();
+
+    for ((edge, splits), group) in edges.iter().zip(splits).zip(edge_groups) {
+        for edge in split(&[*edge], vec![splits]) {
+            split_edges.push(edge);
+            split_groups.push(group);
+        }
+    }
+
+    let rows = Rows::new(&split_edges);
+
+    let mut seen = HashSet::new();
+    let mut windings = vec![0; groups.len()];
+    let mut result = Vec::new();
+
+    for edge in &split_edges {
+        let (k0, k1) = (key(edge.p0), key(edge.p1));
+
+        // overlapping edges are only considered once
+        if !seen.insert((k0.min(k1), k0.max(k1))) {
+            continue;
+        }
+
+        let d = edge.p1 - edge.p0;
+        let m = (edge.p0 + edge.p1) / 2.0;
+
+        // just far enough from the edge to not be rounded onto it
+        let offset = m.abs().max(d.abs()).max_element() * 1e-5;
+        let n = d.perp().normalize() * offset;
+
+        winding(&split_edges, &split_groups, &rows, m + n, &mut windings);
+        let left = inside(&windings);
+
+        winding(&split_edges, &split_groups, &rows, m - n, &mut windings);
+        let right = inside(&windings);
+
+        if left && !right {
+            result.push(*edge);
+        } else if right && !left {
+            result.push(Edge {
+                p0: edge.p1,
+                p1: edge.p0,
+            });
+        }
+    }
+
+    assemble(link(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x0: f32, y0: f32, x1: f32, y1: f32) -> HoledPolygon {
+        HoledPolygon::from(Polygon::from(vec![
+            Vec2::new(x0, y0),
+            Vec2::new(x1, y0),
+            Vec2::new(x1, y1),
+            Vec2::new(x0, y1),
+        ]))
+    }
+
+    fn area(polygons: &[HoledPolygon]) -> f32 {
+        polygons
+            .iter()
+            .map(|polygon| {
+                let holes: f32 = polygon
+                    .holes
+                    .iter()
+                    .map(|hole| signed_area(&hole.points).abs())
+                    .sum();
+
+                signed_area(&polygon.polygon.points) - holes
+            })
+            .sum()
+    }
+
+    fn assert_area(polygons: &[HoledPolygon], expected: f32) {
+        let area = area(polygons);
+
+        assert!((area - expected).abs() < 1e-4, "{} != {}", area, expected);
+    }
+
+    /// Checks `area(A∪B) + area(A∩B) = area(A) + area(B)` and `area(A∖B) + area(A∩B) = area(A)`.
+    fn assert_identities(a: &[HoledPolygon], b: &[HoledPolygon]) {
+        let union = area(&boolean(a, b, BooleanOp::Union));
+        let intersection = area(&boolean(a, b, BooleanOp::Intersection));
+        let difference = area(&boolean(a, b, BooleanOp::Difference));
+        let xor = area(&boolean(a, b, BooleanOp::Xor));
+
+        assert!((union + intersection - area(a) - area(b)).abs() < 1e-4);
+        assert!((difference + intersection - area(a)).abs() < 1e-4);
+        assert!((xor - union + intersection).abs() < 1e-4);
+    }
+
+    #[test]
+    fn overlapping() {
+        let (a, b) = ([rect(0.0, 0.0, 2.0, 2.0)], [rect(1.0, 1.0, 3.0, 3.0)]);
+
+        assert_area(&boolean(&a, &b, BooleanOp::Union), 7.0);
+        assert_area(&boolean(&a, &b, BooleanOp::Intersection), 1.0);
+        assert_area(&boolean(&a, &b, BooleanOp::Difference), 3.0);
+        assert_area(&boolean(&a, &b, BooleanOp::Xor), 6.0);
+
+        assert_eq!(boolean(&a, &b, BooleanOp::Union).len(), 1);
+        assert_eq!(boolean(&a, &b, BooleanOp::Xor).len(), 2);
+        assert_identities(&a, &b);
+    }
+
+    #[test]
+    fn touching() {
+        let (a, b) = ([rect(0.0, 0.0, 1.0, 1.0)], [rect(1.0, 0.0, 2.0, 1.0)]);
+
+        let union = boolean(&a, &b, BooleanOp::Union);
+        assert_eq!(union.len(), 1);
+        assert_area(&union, 2.0);
+
+        assert!(boolean(&a, &b, BooleanOp::Intersection).is_empty());
+        assert_area(&boolean(&a, &b, BooleanOp::Difference), 1.0);
+        assert_identities(&a, &b);
+
+        // only a corner in common
+        let b = [rect(1.0, 1.0, 2.0, 2.0)];
+
+        assert_area(&boolean(&a, &b, BooleanOp::Union), 2.0);
+        assert!(boolean(&a, &b, BooleanOp::Intersection).is_empty());
+        assert_identities(&a, &b);
+    }
+
+    #[test]
+    fn identical() {
+        let (a, b) = ([rect(0.0, 0.0, 1.0, 1.0)], [rect(0.0, 0.0, 1.0, 1.0)]);
+
+        assert_area(&boolean(&a, &b, BooleanOp::Union), 1.0);
+        assert_area(&boolean(&a, &b, BooleanOp::Intersection), 1.0);
+        assert!(boolean(&a, &b, BooleanOp::Difference).is_empty());
+        assert!(boolean(&a, &b, BooleanOp::Xor).is_empty());
+        assert_identities(&a, &b);
+    }
+
+    #[test]
+    fn collinear_edges() {
+        let a = [rect(0.0, 0.0, 2.0, 1.0)];
+
+        // sharing part of the top edge
+        let b = [rect(1.0, 1.0, 3.0, 2.0)];
+
+        let union = boolean(&a, &b, BooleanOp::Union);
+        assert_eq!(union.len(), 1);
+        assert_area(&union, 4.0);
+        assert_identities(&a, &b);
+
+        // sharing the bottom and top edges partially
+        let b = [rect(1.0, 0.0, 3.0, 1.0)];
+
+        assert_area(&boolean(&a, &b, BooleanOp::Union), 3.0);
+        assert_area(&boolean(&a, &b, BooleanOp::Intersection), 1.0);
+        assert_identities(&a, &b);
+
+        // inside, sharing three edges
+        let b = [rect(1.0, 0.0, 2.0, 1.0)];
+
+        assert_area(&boolean(&a, &b, BooleanOp::Union), 2.0);
+        assert_area(&boolean(&a, &b, BooleanOp::Difference), 1.0);
+        assert_identities(&a, &b);
+    }
+
+    #[test]
+    fn holes() {
+        let mut a = rect(0.0, 0.0, 4.0, 4.0);
+        a.holes.push(rect(1.0, 1.0, 3.0, 3.0).polygon);
+
+        let (a, b) = ([a], [rect(2.0, 2.0, 5.0, 5.0)]);
+
+        assert_area(&boolean(&a, &b, BooleanOp::Intersection), 3.0);
+        assert_area(&boolean(&a, &b, BooleanOp::Union), 12.0 + 9.0 - 3.0);
+        assert_identities(&a, &b);
+
+        // filling the hole exactly
+        let b = [rect(1.0, 1.0, 3.0, 3.0)];
+
+        let union = boolean(&a, &b, BooleanOp::Union);
+        assert_eq!(union.len(), 1);
+        assert!(union[0].holes.is_empty());
+        assert_area(&union, 16.0);
+        assert!(boolean(&a, &b, BooleanOp::Intersection).is_empty());
+    }
+
+    #[test]
+    fn rotated() {
+        let diamond = HoledPolygon::from(Polygon::from(vec![
+            Vec2::new(1.0, -0.5),
+            Vec2::new(2.5, 1.0),
+            Vec2::new(1.0, 2.5),
+            Vec2::new(-0.5, 1.0),
+        ]));
+
+        assert_identities(&[rect(0.0, 0.0, 2.0, 2.0)], &[diamond]);
+    }
+
+    #[test]
+    fn overlapping_operand() {
+        // counted once even though the polygons of `a` overlap each other
+        let a = [rect(0.0, 0.0, 2.0, 2.0), rect(1.0, 1.0, 3.0, 3.0)];
+        let b = [rect(2.0, 0.0, 4.0, 1.0)];
+
+        let union = boolean(&a, &b, BooleanOp::Union);
+        assert_eq!(union.len(), 1);
+        assert_area(&union, 9.0);
+
+        assert!(boolean(&a, &b, BooleanOp::Intersection).is_empty());
+        assert_area(&boolean(&a, &b, BooleanOp::Difference), 7.0);
+        assert_area(&boolean(&b, &a, BooleanOp::Union), 9.0);
+    }
+
+    #[test]
+    fn negative_zero() {
+        assert_eq!(key(Vec2::new(-0.0, 0.0)), key(Vec2::ZERO));
+
+        let a = [rect(-0.0, -0.0, 1.0, 1.0)];
+        let b = [rect(0.0, 0.0, 1.0, 1.0)];
+
+        assert!(boolean(&a, &b, BooleanOp::Xor).is_empty());
+        assert_identities(&a, &b);
+    }
+
+    #[test]
+    fn resolve_self_intersection() {
+        // bow tie, both triangles wind the opposite way
+        let ring = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(0.0, 2.0),
+        ];
+
+        let polygons = resolve(&[vec![&ring[..]]], |windings| windings[0] != 0);
+
+        assert_eq!(polygons.len(), 2);
+        assert_area(&polygons, 2.0);
+
+        // square wound twice, only nonzero fills it
+        let square = rect(0.0, 0.0, 1.0, 1.0).polygon.points;
+        let twice = [&square[..], &square[..]];
+
+        assert_area(&resolve(&[twice.to_vec()], |w| w[0] != 0), 1.0);
+        assert!(resolve(&[twice.to_vec()], |w| w[0] % 2 != 0).is_empty());
+    }
+
+    #[test]
+    fn resolve_short_edges() {
+        // points closer than the tolerance, such as offset corners, don't leave gaps
+        let ring = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(2.0 - 2e-6, 2.0 + 2e-6),
+            Vec2::new(0.0, 2.0),
+            Vec2::new(0.0, 3e-6),
+        ];
+
+        let polygons = resolve(&[vec![&ring[..]]], |windings| windings[0] > 0);
+
+        assert_eq!(polygons.len(), 1);
+        assert!(polygons[0].polygon.points.len() <= 5);
+        assert_area(&polygons, 4.0);
+    }
+}
@@ -0,0 +1,317 @@
+//! Decomposition of polygons into convex pieces.
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::{error::Error, polygon::Polygon};
+
+/// Bitwise key of a point, points duplicated by bridging holes share keys.
+#[inline]
+fn key(p: Vec2) -> (u32, u32) {
+    (p.x.to_bits(), p.y.to_bits())
+}
+
+/// Checks if the corner `a`, `b`, `c` doesn't turn clockwise.
+#[inline]
+fn is_convex(a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).perp_dot(c - b) >= 0.0
+}
+
+/// Turns `points` into a convex polygon with all guarantees set.
+#[inline]
+fn convex(points: Vec<Vec2>) -> Polygon {
+    let mut polygon = Polygon::from(points);
+    polygon.clean();
+
+    polygon.is_ccw = Some(true);
+    polygon.is_convex = Some(true);
+    polygon.is_simple = true;
+
+    polygon
+}
+
+/// Triangulates `polygon` and merges triangles as long as the result stays convex, dropping
+/// every diagonal that isn't essential.
+///
+/// `polygon` must be verified, with any holes merged.
+pub(crate) fn hertel_mehlhorn(polygon: &Polygon) -> Result<Vec<Polygon>, Error> {
+    let indices = polygon.try_triangulate()?;
+
+    // triangulation is clockwise
+    let mut pieces: Vec<Option<Vec<Vec2>>> = indices
+        .chunks_exact(3)
+        .map(|t| {
+            Some(vec![
+                polygon.points[t[0]],
+                polygon.points[t[2]],
+                polygon.points[t[1]],
+            ])
+        })
+        .collect();
+
+    let mut edges = HashMap::new();
+
+    for (i, piece) in pieces.iter().enumerate() {
+        let piece = piece.as_ref().unwrap();
+
+        for j in 0..piece.len() {
+            edges.insert((key(piece[j]), key(piece[(j + 1) % piece.len()])), i);
+        }
+    }
+
+    for i in 0..pieces.len() {
+        let mut j = 0;
+
+        // edges of the piece change as it absorbs others, so walk until no more merges happen
+        while j < pieces[i].as_ref().map_or(0, Vec::len) {
+            let piece = pieces[i].as_ref().unwrap();
+            let len = piece.len();
+
+            let (a, b) = (piece[j], piece[(j + 1) % len]);
+
+            let other = match edges.get(&(key(b), key(a))) {
+                Some(&other) if other != i => other,
+                _ => {
+                    j += 1;
+                    continue;
+                }
+            };
+
+            let neighbour = pieces[other].as_ref().unwrap();
+            let n = neighbour.len();
+            let k = (0..n)
+                .find(|&k| key(neighbour[k]) == key(b) && key(neighbour[(k + 1) % n]) == key(a))
+                .unwrap();
+
+            // both corners at the ends of the removed diagonal must stay convex
+            let a_convex = is_convex(piece[(j + len - 1) % len], a, neighbour[(k + 2) % n]);
+            let b_convex = is_convex(neighbour[(k + n - 1) % n], b, piece[(j + 2) % len]);
+
+            if !a_convex || !b_convex {
+                j += 1;
+                continue;
+            }
+
+            // walk self from b to a, then the neighbour from after a to before b
+            let mut merged = Vec::with_capacity(len + n - 2);
+            merged.extend((0..len).map(|l| piece[(j + 1 + l) % len]));
+            merged.extend((0..n - 2).map(|l| neighbour[(k + 2 + l) % n]));
+
+            edges.remove(&(key(a), key(b)));
+            edges.remove(&(key(b), key(a)));
+
+            for l in 0..merged.len() {
+                let edge = (key(merged[l]), key(merged[(l + 1) % merged.len()]));
+
+                if let Some(piece) = edges.get_mut(&edge) {
+                    *piece = i;
+                }
+            }
+
+            pieces[other] = None;
+            pieces[i] = Some(merged);
+
+            j = 0;
+        }
+    }
+
+    Ok(pieces.into_iter().flatten().map(convex).collect())
+}
+
+/// Maximum recursion depth of [`bayazit`], after which the remaining pieces are decomposed by
+/// [`hertel_mehlhorn`].
+const MAX_DEPTH: usize = 256;
+
+/// Decomposes `polygon` by repeatedly splitting it at reflex vertices, preferring diagonals
+/// to existing vertices and adding new points otherwise, which often results in fewer pieces
+/// than [`hertel_mehlhorn`].
+///
+/// `polygon` must be verified, with any holes merged.
+pub(crate) fn bayazit(polygon: &Polygon) -> Result<Vec<Polygon>, Error> {
+    let mut pieces = Vec::new();
+
+    split(&polygon.points, &mut pieces, 0)?;
+
+    Ok(pieces)
+}
+
+/// Intersection of the lines through `p0`, `p1` and `q0`, `q1`.
+#[inline]
+fn line_intersection(p0: Vec2, p1: Vec2, q0: Vec2, q1: Vec2) -> Option<Vec2> {
+    let r = p1 - p0;
+    let s = q1 - q0;
+
+    let d = r.perp_dot(s);
+
+    if d == 0.0 {
+        None
+    } else {
+        Some(p0 + r * ((q0 - p0).perp_dot(s) / d))
+    }
+}
+
+/// Checks if the segments `p0`, `p1` and `q0`, `q1` intersect, including touching.
+#[inline]
+fn segments_intersect(p0: Vec2, p1: Vec2, q0: Vec2, q1: Vec2) -> bool {
+    let r = p1 - p0;
+    let s = q1 - q0;
+
+    let d = r.perp_dot(s);
+
+    if d == 0.0 {
+        return false;
+    }
+
+    let t = (q0 - p0).perp_dot(s) / d;
+    let u = (q0 - p0).perp_dot(r) / d;
+
+    (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)
+}
+
+fn split(points: &[Vec2], pieces: &mut Vec<Polygon>, depth: usize) -> Result<(), Error> {
+    let len = points.len();
+
+    if len < 3 {
+        return Ok(());
+    }
+
+    if depth > MAX_DEPTH {
+        let mut polygon = Polygon::from(points.to_vec());
+        polygon.is_ccw = Some(true);
+        polygon.is_simple = true;
+
+        pieces.append(&mut hertel_mehlhorn(&polygon)?);
+
+        return Ok(());
+    }
+
+    let at = |i: usize| points[i % len];
+    let prev = |i: usize| points[(i + len - 1) % len];
+    let side = |a: Vec2, b: Vec2, c: Vec2| (b - a).perp_dot(c - a);
+
+    let reflex = match (0..len).find(|&i| side(prev(i), at(i), at(i + 1)) < 0.0) {
+        Some(reflex) => reflex,
+        None => {
+            pieces.push(convex(points.to_vec()));
+            return Ok(());
+        }
+    };
+
+    let i = reflex;
+    let p = at(i);
+
+    let mut lower = (f32::INFINITY, Vec2::ZERO, 0);
+    let mut upper = (f32::INFINITY, Vec2::ZERO, 0);
+
+    // find the closest edges hit by extending the edges at the reflex vertex
+    for j in 0..len {
+        if side(prev(i), p, at(j)) > 0.0 && side(prev(i), p, prev(j)) <= 0.0 {
+            if let Some(q) = line_intersection(prev(i), p, at(j), prev(j)) {
+                if side(at(i + 1), p, q) < 0.0 && p.distance_squared(q) < lower.0 {
+                    lower = (p.distance_squared(q), q, j);
+                }
+            }
+        }
+
+        if side(at(i + 1), p, at(j + 1)) > 0.0 && side(at(i + 1), p, at(j)) <= 0.0 {
+            if let Some(q) = line_intersection(at(i + 1), p, at(j), at(j + 1)) {
+                if side(prev(i), p, q) > 0.0 && p.distance_squared(q) < upper.0 {
+                    upper = (p.distance_squared(q), q, j);
+                }
+            }
+        }
+    }
+
+    let (_, lower_point, lower_index) = lower;
+    let (_, upper_point, mut upper_index) = upper;
+
+    let mut lower_points = Vec::new();
+    let mut upper_points = Vec::new();
+
+    if lower_index == (upper_index + 1) % len {
+        // no vertex to connect to, split at a new point between the intersections
+        let steiner = (lower_point + upper_point) / 2.0;
+
+        if i < upper_index {
+            lower_points.extend_from_slice(&points[i..=upper_index]);
+            lower_points.push(steiner);
+            upper_points.push(steiner);
+
+            if lower_index != 0 {
+                upper_points.extend_from_slice(&points[lower_index..]);
+            }
+
+            upper_points.extend_from_slice(&points[..=i]);
+        } else {
+            if i != 0 {
+                lower_points.extend_from_slice(&points[i..]);
+            }
+
+            lower_points.extend_from_slice(&points[..=upper_index]);
+            lower_points.push(steiner);
+            upper_points.push(steiner);
+            upper_points.extend_from_slice(&points[lower_index..=i]);
+        }
+    } else {
+        // connect to the closest visible vertex between the intersections
+        if lower_index > upper_index {
+            upper_index += len;
+        }
+
+        let mut closest = (f32::INFINITY, None);
+
+        for j in lower_index..=upper_index {
+            let q = at(j);
+
+            if side(prev(i), p, q) >= 0.0 && side(at(i + 1), p, q) <= 0.0 {
+                let distance = p.distance_squared(q);
+
+                if distance < closest.0 && can_see(points, i, j % len) {
+                    closest = (distance, Some(j % len));
+                }
+            }
+        }
+
+        let closest = match closest.1 {
+            Some(closest) => closest,
+            None => return Err(Error::Degenerate("no diagonal found for reflex vertex")),
+        };
+
+        if i < closest {
+            lower_points.extend_from_slice(&points[i..=closest]);
+
+            if closest != 0 {
+                upper_points.extend_from_slice(&points[closest..]);
+            }
+
+            upper_points.extend_from_slice(&points[..=i]);
+        } else {
+            if i != 0 {
+                lower_points.extend_from_slice(&points[i..]);
+            }
+
+            lower_points.extend_from_slice(&points[..=closest]);
+            upper_points.extend_from_slice(&points[closest..=i]);
+        }
+    }
+
+    split(&lower_points, pieces, depth + 1)?;
+    split(&upper_points, pieces, depth + 1)
+}
+
+/// Checks if the diagonal from `a` to `b` doesn't cross any edge not adjacent to either.
+#[inline]
+fn can_see(points: &[Vec2], a: usize, b: usize) -> bool {
+    let len = points.len();
+
+    (0..len).all(|i| {
+        let j = (i + 1) % len;
+
+        i == a
+            || i == b
+            || j == a
+            || j == b
+            || !segments_intersect(points[a], points[b], points[i], points[j])
+    })
+}
@@ -0,0 +1,757 @@
+//! Constrained Delaunay triangulation.
+//!
+//! Points are inserted one at a time into a super triangle, flipping edges to keep the
+//! triangulation Delaunay. The boundary edges are then forced in by flipping the edges crossing
+//! them, and finally the triangles are classified by winding number, found by walking from the
+//! super triangle and counting the boundary edges crossed.
+
+use std::collections::{HashMap, VecDeque};
+
+use glam::{DVec2, Vec2};
+
+use crate::error::Error;
+
+const NONE: usize = usize::MAX;
+
+/// Positive if `c` is left of the line from `a` to `b`.
+#[inline]
+fn orient(a: DVec2, b: DVec2, c: DVec2) -> f64 {
+    (b - a).perp_dot(c - a)
+}
+
+/// Positive if `d` is inside the circumcircle of the counter clockwise triangle `a`, `b`, `c`.
+#[inline]
+fn incircle(a: DVec2, b: DVec2, c: DVec2, d: DVec2) -> f64 {
+    let ad = a - d;
+    let bd = b - d;
+    let cd = c - d;
+
+    let ad2 = ad.length_squared();
+    let bd2 = bd.length_squared();
+    let cd2 = cd.length_squared();
+
+    ad.x * (bd.y * cd2 - bd2 * cd.y) - ad.y * (bd.x * cd2 - bd2 * cd.x)
+        + ad2 * (bd.x * cd.y - bd.y * cd.x)
+}
+
+/// Whether segment `a`, `b` and segment `c`, `d` cross at a single point inside both.
+#[inline]
+fn crosses(a: DVec2, b: DVec2, c: DVec2, d: DVec2) -> bool {
+    orient(a, b, c) * orient(a, b, d) < 0.0 && orient(c, d, a) * orient(c, d, b) < 0.0
+}
+
+struct Cdt {
+    points: Vec<DVec2>,
+    /// Counter clockwise vertices of every triangle.
+    triangles: Vec<[usize; 3]>,
+    /// The triangle across edge `i`, from vertex `i` to vertex `i + 1`.
+    adjacent: Vec<[usize; 3]>,
+    /// A triangle containing each vertex.
+    vertex_triangle: Vec<usize>,
+    /// Number of times each directed edge appears in the boundary.
+    constraints: HashMap<(usize, usize), i32>,
+    last: usize,
+}
+
+impl Cdt {
+    /// Creates a super triangle containing every point, the super vertices are the last three.
+    fn new(mut points: Vec<DVec2>) -> Self {
+        let min = points
+            .iter()
+            .fold(DVec2::splat(f64::INFINITY), |a, b| a.min(*b));
+        let max = points
+            .iter()
+            .fold(DVec2::splat(f64::NEG_INFINITY), |a, b| a.max(*b));
+
+        let center = (min + max) / 2.0;
+        let size = (max - min).max_element().max(f64::EPSILON);
+
+        let n = points.len();
+
+        points.push(center + DVec2::new(-10.0, -10.0) * size);
+        points.push(center + DVec2::new(10.0, -10.0) * size);
+        points.push(center + DVec2::new(0.0, 10.0) * size);
+
+        let mut vertex_triangle = vec![NONE; points.len()];
+        vertex_triangle[n..].fill(0);
+
+        Self {
+            points,
+            triangles: vec![[n, n + 1, n + 2]],
+            adjacent: vec![[NONE; 3]],
+            vertex_triangle,
+            constraints: HashMap::new(),
+            last: 0,
+        }
+    }
+
+    #[inline]
+    fn set(&mut self, t: usize, triangle: [usize; 3], adjacent: [usize; 3]) {
+        if t == self.triangles.len() {
+            self.triangles.push(triangle);
+            self.adjacent.push(adjacent);
+        } else {
+            self.triangles[t] = triangle;
+            self.adjacent[t] = adjacent;
+        }
+
+        for v in triangle {
+            self.vertex_triangle[v] = t;
+        }
+    }
+
+    /// Points the adjacency of `t` at `old` to `new`.
+    #[inline]
+    fn replace(&mut self, t: usize, old: usize, new: usize) {
+        if t == NONE {
+            return;
+        }
+
+        for adjacent in &mut self.adjacent[t] {
+            if *adjacent == old {
+                *adjacent = new;
+            }
+        }
+    }
+
+    #[inline]
+    fn index(&self, t: usize, v: usize) -> usize {
+        self.triangles[t].iter().position(|u| *u == v).unwrap()
+    }
+
+    #[inline]
+    fn is_constraint(&self, u: usize, w: usize) -> bool {
+        self.constraints.contains_key(&(u, w)) || self.constraints.contains_key(&(w, u))
+    }
+
+    /// Finds the triangle containing `p`, and the edge if `p` is on one.
+    fn locate(&self, p: DVec2) -> Option<(usize, Option<usize>)> {
+        let mut t = self.last;
+
+        // walk towards p, which terminates in a delaunay triangulation
+        'walk: for _ in 0..self.triangles.len() {
+            let triangle = self.triangles[t];
+
+            for i in 0..3 {
+                let a = self.points[triangle[i]];
+                let b = self.points[triangle[(i + 1) % 3]];
+
+                if orient(a, b, p) < 0.0 {
+                    let n = self.adjacent[t][i];
+
+                    if n == NONE {
+                        return None;
+                    }
+
+                    t = n;
+                    continue 'walk;
+                }
+            }
+
+            let edge = (0..3).find(|i| {
+                let a = self.points[triangle[*i]];
+                let b = self.points[triangle[(i + 1) % 3]];
+
+                orient(a, b, p) == 0.0
+            });
+
+            return Some((t, edge));
+        }
+
+        // the walk can cycle on nearly degenerate input, fall back to testing every triangle
+        (0..self.triangles.len()).find_map(|t| {
+            let triangle = self.triangles[t];
+
+            let sides: Vec<f64> = (0..3)
+                .map(|i| {
+                    let a = self.points[triangle[i]];
+                    let b = self.points[triangle[(i + 1) % 3]];
+
+                    orient(a, b, p)
+                })
+                .collect();
+
+            if sides.iter().all(|side| *side >= 0.0) {
+                Some((t, sides.iter().position(|side| *side == 0.0)))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Whether edge `i` of `t` isn't locally delaunay.
+    #[inline]
+    fn is_illegal(&self, t: usize, i: usize) -> bool {
+        let n = self.adjacent[t][i];
+
+        if n == NONE {
+            return false;
+        }
+
+        let triangle = self.triangles[t];
+
+        if self.is_constraint(triangle[i], triangle[(i + 1) % 3]) {
+            return false;
+        }
+
+        let j = self.index(n, triangle[(i + 1) % 3]);
+        let q = self.triangles[n][(j + 2) % 3];
+
+        incircle(
+            self.points[triangle[0]],
+            self.points[triangle[1]],
+            self.points[triangle[2]],
+            self.points[q],
+        ) > 0.0
+    }
+
+    /// Flips edge `i` of `t`, afterwards `t` is `[p, a, q]` and the neighbor `[q, b, p]`, where
+    /// `a`, `b` is the old edge, `p` the vertex of `t` opposite it and `q` that of the neighbor.
+    fn flip(&mut self, t: usize, i: usize) {
+        let triangle = self.triangles[t];
+
+        let a = triangle[i];
+        let b = triangle[(i + 1) % 3];
+        let p = triangle[(i + 2) % 3];
+
+        let n = self.adjacent[t][i];
+        let j = self.index(n, b);
+        let q = self.triangles[n][(j + 2) % 3];
+
+        let tb = self.adjacent[t][(i + 1) % 3];
+        let ta = self.adjacent[t][(i + 2) % 3];
+        let na = self.adjacent[n][(j + 1) % 3];
+        let nb = self.adjacent[n][(j + 2) % 3];
+
+        self.set(t, [p, a, q], [ta, na, n]);
+        self.set(n, [q, b, p], [nb, tb, t]);
+
+        self.replace(na, n, t);
+        self.replace(tb, t, n);
+    }
+
+    /// Flips illegal edges opposite the newly inserted vertex.
+    fn legalize(&mut self, mut stack: Vec<(usize, usize)>) {
+        while let Some((t, i)) = stack.pop() {
+            if self.is_illegal(t, i) {
+                let n = self.adjacent[t][i];
+
+                self.flip(t, i);
+
+                stack.push((t, 1));
+                stack.push((n, 0));
+            }
+        }
+    }
+
+    fn insert(&mut self, v: usize) -> Result<(), Error> {
+        let (t, edge) = self.locate(self.points[v]).ok_or(Error::Triangulation)?;
+
+        let [a, b, c] = self.triangles[t];
+        let [nab, nbc, nca] = self.adjacent[t];
+
+        match edge {
+            None => {
+                let t1 = self.triangles.len();
+                let t2 = t1 + 1;
+
+                self.set(t, [a, b, v], [nab, t1, t2]);
+                self.set(t1, [b, c, v], [nbc, t2, t]);
+                self.set(t2, [c, a, v], [nca, t, t1]);
+
+                self.replace(nbc, t, t1);
+                self.replace(nca, t, t2);
+
+                self.legalize(vec![(t, 0), (t1, 0), (t2, 0)]);
+            }
+            Some(i) => {
+                let triangle = self.triangles[t];
+                let adjacent = self.adjacent[t];
+
+                let a = triangle[i];
+                let b = triangle[(i + 1) % 3];
+                let c = triangle[(i + 2) % 3];
+
+                let n = adjacent[i];
+                let nbc = adjacent[(i + 1) % 3];
+                let nca = adjacent[(i + 2) % 3];
+
+                let t1 = self.triangles.len();
+
+                if n == NONE {
+                    self.set(t, [c, a, v], [nca, NONE, t1]);
+                    self.set(t1, [b, c, v], [nbc, t, NONE]);
+
+                    self.replace(nbc, t, t1);
+
+                    self.legalize(vec![(t, 0), (t1, 0)]);
+                } else {
+                    let j = self.index(n, b);
+                    let d = self.triangles[n][(j + 2) % 3];
+                    let nad = self.adjacent[n][(j + 1) % 3];
+                    let ndb = self.adjacent[n][(j + 2) % 3];
+
+                    let n1 = t1 + 1;
+
+                    self.set(t, [c, a, v], [nca, n, t1]);
+                    self.set(t1, [b, c, v], [nbc, t, n1]);
+                    self.set(n, [a, d, v], [nad, n1, t]);
+                    self.set(n1, [d, b, v], [ndb, t1, n]);
+
+                    self.replace(nbc, t, t1);
+                    self.replace(ndb, n, n1);
+
+                    self.legalize(vec![(t, 0), (t1, 0), (n, 0), (n1, 0)]);
+                }
+            }
+        }
+
+        self.last = t;
+
+        Ok(())
+    }
+
+    /// Triangles containing `v`.
+    fn around(&self, v: usize) -> Vec<usize> {
+        let start = self.vertex_triangle[v];
+        let mut triangles = vec![start];
+
+        // rotate counter clockwise, then clockwise if a boundary is hit
+        let mut t = start;
+
+        loop {
+            t = self.adjacent[t][(self.index(t, v) + 2) % 3];
+
+            if t == start {
+                return triangles;
+            }
+
+            if t == NONE {
+                break;
+            }
+
+            triangles.push(t);
+        }
+
+        let mut t = start;
+
+        loop {
+            t = self.adjacent[t][self.index(t, v)];
+
+            if t == NONE {
+                return triangles;
+            }
+
+            triangles.push(t);
+        }
+    }
+
+    /// Finds the triangle with the directed edge `u`, `w`.
+    #[inline]
+    fn find_edge(&self, u: usize, w: usize) -> Option<(usize, usize)> {
+        self.around(u).into_iter().find_map(|t| {
+            let i = self.index(t, u);
+
+            if self.triangles[t][(i + 1) % 3] == w {
+                Some((t, i))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Collects the edges crossed by the segment from `a` towards `b`, stopping at `b` or at the
+    /// first vertex on the segment, which is returned.
+    fn trace(&self, a: usize, b: usize, crossed: &mut Vec<(usize, usize)>) -> Result<usize, Error> {
+        let pa = self.points[a];
+        let pb = self.points[b];
+
+        let mut start = None;
+
+        for t in self.around(a) {
+            let k = self.index(t, a);
+            let u = self.triangles[t][(k + 1) % 3];
+            let w = self.triangles[t][(k + 2) % 3];
+
+            for x in [u, w] {
+                let px = self.points[x];
+
+                if x == b || (orient(pa, pb, px) == 0.0 && (px - pa).dot(pb - pa) > 0.0) {
+                    return Ok(x);
+                }
+            }
+
+            if orient(pa, self.points[u], pb) > 0.0 && orient(pa, self.points[w], pb) < 0.0 {
+                start = Some((t, (k + 1) % 3));
+            }
+        }
+
+        let (mut t, mut i) = start.ok_or(Error::Triangulation)?;
+
+        loop {
+            let u = self.triangles[t][i];
+            let w = self.triangles[t][(i + 1) % 3];
+
+            // constraints crossing each other means the boundary intersects itself
+            if self.is_constraint(u, w) {
+                return Err(Error::Triangulation);
+            }
+
+            crossed.push((u, w));
+
+            let n = self.adjacent[t][i];
+
+            if n == NONE {
+                return Err(Error::Triangulation);
+            }
+
+            let j = self.index(n, w);
+            let x = self.triangles[n][(j + 2) % 3];
+
+            if x == b {
+                return Ok(b);
+            }
+
+            // u is right of the segment and w left
+            let side = orient(pa, pb, self.points[x]);
+
+            if side == 0.0 {
+                return Ok(x);
+            }
+
+            t = n;
+            i = if side > 0.0 { (j + 1) % 3 } else { (j + 2) % 3 };
+        }
+    }
+
+    /// Flips the `crossed` edges until the edge `a`, `b` exists, then restores the delaunay
+    /// property around it.
+    fn force(&mut self, a: usize, b: usize, crossed: Vec<(usize, usize)>) -> Result<(), Error> {
+        let pa = self.points[a];
+        let pb = self.points[b];
+
+        let limit = crossed.len() * crossed.len() * 4 + 16;
+        let mut queue = VecDeque::from(crossed);
+        let mut new_edges = Vec::new();
+
+        let mut iterations = 0;
+
+        while let Some((u, w)) = queue.pop_front() {
+            iterations += 1;
+
+            if iterations > limit {
+                return Err(Error::Triangulation);
+            }
+
+            let (t, i) = self.find_edge(u, w).ok_or(Error::Triangulation)?;
+
+            let p = self.triangles[t][(i + 2) % 3];
+            let n = self.adjacent[t][i];
+            let q = self.triangles[n][(self.index(n, w) + 2) % 3];
+
+            let pp = self.points[p];
+            let pq = self.points[q];
+
+            // only convex quadrilaterals can be flipped
+            if orient(pp, pq, self.points[u]) * orient(pp, pq, self.points[w]) >= 0.0 {
+                queue.push_back((u, w));
+                continue;
+            }
+
+            self.flip(t, i);
+
+            if crosses(pa, pb, pp, pq) {
+                queue.push_back((p, q));
+            } else {
+                new_edges.push((p, q));
+            }
+        }
+
+        let mut iterations = 0;
+        let mut flipped = true;
+
+        while flipped {
+            flipped = false;
+            iterations += 1;
+
+            if iterations > limit {
+                return Err(Error::Triangulation);
+            }
+
+            for edge in &mut new_edges {
+                let (u, w) = *edge;
+
+                if (u, w) == (a, b) || (w, u) == (a, b) {
+                    continue;
+                }
+
+                let (t, i) = self.find_edge(u, w).ok_or(Error::Triangulation)?;
+
+                if self.is_illegal(t, i) {
+                    let p = self.triangles[t][(i + 2) % 3];
+                    let n = self.adjacent[t][i];
+                    let q = self.triangles[n][(self.index(n, w) + 2) % 3];
+
+                    self.flip(t, i);
+
+                    *edge = (p, q);
+                    flipped = true;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the boundary edge from `a` to `b`.
+    fn constrain(&mut self, mut a: usize, b: usize) -> Result<(), Error> {
+        while a != b {
+            let mut crossed = Vec::new();
+            let next = self.trace(a, b, &mut crossed)?;
+
+            if !crossed.is_empty() {
+                self.force(a, next, crossed)?;
+            }
+
+            *self.constraints.entry((a, next)).or_default() += 1;
+
+            a = next;
+        }
+
+        Ok(())
+    }
+
+    /// Winding number of every triangle.
+    fn winding(&self) -> Vec<i32> {
+        let mut winding = vec![None; self.triangles.len()];
+
+        let start = self.vertex_triangle[self.points.len() - 1];
+        winding[start] = Some(0);
+
+        let mut stack = vec![start];
+
+        while let Some(t) = stack.pop() {
+            let w = winding[t].unwrap();
+
+            for i in 0..3 {
+                let n = self.adjacent[t][i];
+
+                if n == NONE || winding[n].is_some() {
+                    continue;
+                }
+
+                let u = self.triangles[t][i];
+                let v = self.triangles[t][(i + 1) % 3];
+
+                // t is left of the edge, boundaries wind counter clockwise around their inside
+                let count = |e| self.constraints.get(&e).copied().unwrap_or(0);
+
+                winding[n] = Some(w + count((v, u)) - count((u, v)));
+                stack.push(n);
+            }
+        }
+
+        winding.into_iter().map(|w| w.unwrap_or(0)).collect()
+    }
+}
+
+/// Triangulates the area inside `rings` with the even-odd rule, returning indices into the
+/// rings concatenated.
+///
+/// Triangles are emitted in the same winding as [`Polygon::triangulate`](crate::Polygon).
+pub(crate) fn triangulate(rings: &[&[Vec2]]) -> Result<Vec<usize>, Error> {
+    // deduplicate points, rings may share vertices
+    let mut unique = HashMap::new();
+    let mut points = Vec::new();
+    let mut original = Vec::new();
+    let mut map = Vec::new();
+
+    for (i, point) in rings.iter().flat_map(|ring| ring.iter()).enumerate() {
+        crate::error::finite(&[*point])?;
+
+        let v = *unique
+            .entry((point.x.to_bits(), point.y.to_bits()))
+            .or_insert_with(|| {
+                points.push(point.as_f64());
+                original.push(i);
+                points.len() - 1
+            });
+
+        map.push(v);
+    }
+
+    if points.len() < 3 {
+        return Err(Error::Degenerate("polygon must have at least three points"));
+    }
+
+    let n = points.len();
+    let mut cdt = Cdt::new(points);
+
+    for v in 0..n {
+        cdt.insert(v)?;
+    }
+
+    let mut offset = 0;
+
+    for ring in rings {
+        for i in 0..ring.len() {
+            let a = map[offset + i];
+            let b = map[offset + (i + 1) % ring.len()];
+
+            if a != b {
+                cdt.constrain(a, b)?;
+            }
+        }
+
+        offset += ring.len();
+    }
+
+    let mut indices = Vec::new();
+
+    for (triangle, winding) in cdt.triangles.iter().zip(cdt.winding()) {
+        if winding % 2 == 0 || triangle.iter().any(|v| *v >= n) {
+            continue;
+        }
+
+        indices.push(original[triangle[0]]);
+        indices.push(original[triangle[2]]);
+        indices.push(original[triangle[1]]);
+    }
+
+    Ok(indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, f32::consts::TAU};
+
+    use crate::polygon::Polygon;
+
+    use super::*;
+
+    fn signed_area(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+        (b - a).perp_dot(c - a) / 2.0
+    }
+
+    fn ring_area(ring: &[Vec2]) -> f32 {
+        (0..ring.len())
+            .map(|i| ring[i].perp_dot(ring[(i + 1) % ring.len()]) / 2.0)
+            .sum::<f32>()
+            .abs()
+    }
+
+    fn circle(center: Vec2, radius: f32, n: usize) -> Vec<Vec2> {
+        (0..n)
+            .map(|i| {
+                let a = i as f32 / n as f32 * TAU;
+
+                center + Vec2::new(a.cos(), a.sin()) * radius
+            })
+            .collect()
+    }
+
+    /// Triangulates `rings` and checks the area, constraint edges and winding.
+    fn check(rings: &[&[Vec2]]) {
+        let indices = triangulate(rings).unwrap();
+        let points: Vec<Vec2> = rings.iter().flat_map(|ring| ring.iter().copied()).collect();
+
+        let expected = ring_area(rings[0]) - rings[1..].iter().map(|r| ring_area(r)).sum::<f32>();
+
+        let mut area = 0.0;
+        let mut edges = HashSet::new();
+
+        for triangle in indices.chunks_exact(3) {
+            let (a, b, c) = (triangle[0], triangle[1], triangle[2]);
+
+            // clockwise, like ear clipping
+            let signed = signed_area(points[a], points[b], points[c]);
+            assert!(signed < 0.0, "triangle {:?} isn't clockwise", triangle);
+
+            area -= signed;
+
+            for (i, j) in [(a, b), (b, c), (c, a)].iter().copied() {
+                edges.insert((i.min(j), i.max(j)));
+            }
+        }
+
+        assert!((area - expected).abs() < 1e-3, "{} != {}", area, expected);
+
+        let mut offset = 0;
+
+        for ring in rings {
+            for i in 0..ring.len() {
+                let (a, b) = (offset + i, offset + (i + 1) % ring.len());
+
+                let edge = (a.min(b), a.max(b));
+
+                assert!(edges.contains(&edge), "missing edge {:?}", edge);
+            }
+
+            offset += ring.len();
+        }
+    }
+
+    #[test]
+    fn square() {
+        check(&[&[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ]]);
+    }
+
+    #[test]
+    fn concave() {
+        // comb with notches cut in from the top, its edges cross the convex hull
+        let mut comb = vec![Vec2::new(0.0, 0.0), Vec2::new(9.0, 0.0)];
+
+        for i in (0..5).rev() {
+            let x = i as f32 * 2.0;
+
+            comb.push(Vec2::new(x + 1.0, 5.0));
+            comb.push(Vec2::new(x + 0.5, 1.0));
+            comb.push(Vec2::new(x, 5.0));
+        }
+
+        check(&[&comb]);
+
+        // the same winding as ear clipping
+        let ear_clipping = Polygon::from(comb.clone()).triangulate();
+        let (a, b, c) = (ear_clipping[0], ear_clipping[1], ear_clipping[2]);
+
+        assert!(signed_area(comb[a], comb[b], comb[c]) < 0.0);
+    }
+
+    #[test]
+    fn holes() {
+        let outer = circle(Vec2::ZERO, 10.0, 32);
+        let mut a = circle(Vec2::new(-4.0, 0.0), 2.0, 12);
+        let mut b = circle(Vec2::new(4.0, 1.0), 3.0, 16);
+
+        a.reverse();
+        b.reverse();
+
+        check(&[&outer, &a]);
+        check(&[&outer, &a, &b]);
+    }
+
+    #[test]
+    fn holes_either_winding() {
+        let outer = circle(Vec2::ZERO, 10.0, 32);
+        let hole = circle(Vec2::ZERO, 5.0, 7);
+
+        check(&[&outer, &hole]);
+    }
+
+    #[test]
+    fn thin_slivers() {
+        // long thin polygon, where a plain delaunay triangulation would miss the edges
+        let ring: Vec<Vec2> = (0..20)
+            .map(|i| Vec2::new(i as f32, (i % 2) as f32 * 0.01))
+            .chain((0..20).rev().map(|i| Vec2::new(i as f32, 0.5)))
+            .collect();
+
+        check(&[&ring]);
+    }
+}
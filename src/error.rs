@@ -0,0 +1,63 @@
+use std::fmt;
+
+use glam::Vec2;
+
+/// Error produced by [`Shape::try_generate`](crate::Shape::try_generate).
+///
+/// # Example
+/// ```
+/// # use scissor::{prelude::*, Config, Error, Polygon};
+/// let polygon = Polygon::from(vec![Vec2::ZERO, Vec2::X]);
+///
+/// let result = Id::<Polygon>::new()
+///     .fill([1.0; 4])
+///     .try_generate(&Config::default(), polygon);
+///
+/// assert!(matches!(result, Err(Error::Degenerate(_))));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// Input doesn't have enough points or has no extent, eg. a polygon with two points.
+    Degenerate(&'static str),
+    /// No ear could be found while triangulating, usually caused by a non simple polygon.
+    Triangulation,
+    /// A hole isn't inside the polygon it should be merged with.
+    HoleOutsidePolygon,
+    /// A coordinate is NaN or infinite.
+    NonFinite,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Degenerate(reason) => write!(f, "degenerate input: {}", reason),
+            Self::Triangulation => write!(f, "triangulation failed, could not find ear"),
+            Self::HoleOutsidePolygon => write!(f, "hole lies outside of polygon"),
+            Self::NonFinite => write!(f, "coordinates must be finite"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Checks that all `points` are finite.
+#[inline]
+pub(crate) fn finite(points: &[Vec2]) -> Result<(), Error> {
+    if points.iter().all(|p| p.is_finite()) {
+        Ok(())
+    } else {
+        Err(Error::NonFinite)
+    }
+}
+
+/// Checks that `value` is finite and positive, `reason` describes the requirement otherwise.
+#[inline]
+pub(crate) fn positive(value: f32, reason: &'static str) -> Result<(), Error> {
+    if !value.is_finite() {
+        Err(Error::NonFinite)
+    } else if value <= 0.0 {
+        Err(Error::Degenerate(reason))
+    } else {
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
-use glam::Vec2;
+use glam::{Affine2, Mat2, Vec2};
 
-use crate::{mesh::Mesh, polygon::Polygon, polyline::Polyline, shapes::*, Shape};
+use crate::{mesh::Mesh, paint::Paint, polygon::Polygon, polyline::Polyline, shapes::*, Shape};
 
 /// Extension trait to [`Shape`] to make code simpler to write.
 pub trait ShapeExt: Shape + Sized {
@@ -49,6 +49,42 @@ pub trait ShapeExt: Shape + Sized {
         }
     }
 
+    /// Adds a quadratic bézier curve from the end of the line to `end`.
+    #[inline]
+    fn quad_to(self, ctrl: impl Into<Vec2>, end: impl Into<Vec2>) -> Combine<Self, QuadTo>
+    where
+        Self: Shape<Output = Polyline>,
+    {
+        Combine {
+            input: self,
+            output: QuadTo {
+                ctrl: ctrl.into(),
+                end: end.into(),
+            },
+        }
+    }
+
+    /// Adds a cubic bézier curve from the end of the line to `end`.
+    #[inline]
+    fn cubic_to(
+        self,
+        c1: impl Into<Vec2>,
+        c2: impl Into<Vec2>,
+        end: impl Into<Vec2>,
+    ) -> Combine<Self, CubicTo>
+    where
+        Self: Shape<Output = Polyline>,
+    {
+        Combine {
+            input: self,
+            output: CubicTo {
+                c1: c1.into(),
+                c2: c2.into(),
+                end: end.into(),
+            },
+        }
+    }
+
     #[inline]
     fn offset(self, offset: f32) -> Combine<Self, Offset<Self::Output>>
     where
@@ -62,13 +98,52 @@ pub trait ShapeExt: Shape + Sized {
 
     /// Thickens line, potentially rounded.
     #[inline]
-    fn thicken(self, thickness: f32, round: bool) -> Combine<Self, Thicken>
+    fn thicken(self, thickness: f32, round: bool) -> Combine<Self, Thicken<Self::Output>>
+    where
+        Thicken<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Thicken::new(thickness).cap(if round { LineCap::Round } else { LineCap::Butt }),
+        }
+    }
+
+    /// Thickens line with the style of `thicken`, see [`Thicken`].
+    #[inline]
+    fn stroke(self, thicken: Thicken<Self::Output>) -> Combine<Self, Thicken<Self::Output>>
+    where
+        Thicken<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: thicken,
+        }
+    }
+
+    /// Thickens line with a width varying along it, see [`VariableThicken`].
+    #[inline]
+    fn thicken_variable<W: Width>(self, width: W) -> Combine<Self, VariableThicken<W>>
+    where
+        Self: Shape<Output = Polyline>,
+    {
+        Combine {
+            input: self,
+            output: VariableThicken::new(width),
+        }
+    }
+
+    /// Thickens line with the width and style of `thicken`, see [`VariableThicken`].
+    #[inline]
+    fn stroke_variable<W: Width>(
+        self,
+        thicken: VariableThicken<W>,
+    ) -> Combine<Self, VariableThicken<W>>
     where
         Self: Shape<Output = Polyline>,
     {
         Combine {
             input: self,
-            output: Thicken { thickness, round },
+            output: thicken,
         }
     }
 
@@ -96,6 +171,203 @@ pub trait ShapeExt: Shape + Sized {
         }
     }
 
+    /// Union of self and `other`, the area covered by either.
+    #[inline]
+    fn union<S>(self, other: S) -> Combine<Self, Boolean<Self::Output, S>>
+    where
+        Boolean<Self::Output, S>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Boolean::new(other, BooleanOp::Union),
+        }
+    }
+
+    /// Intersection of self and `other`, the area covered by both.
+    #[inline]
+    fn intersect<S>(self, other: S) -> Combine<Self, Boolean<Self::Output, S>>
+    where
+        Boolean<Self::Output, S>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Boolean::new(other, BooleanOp::Intersection),
+        }
+    }
+
+    /// Difference of self and `other`, the area covered by self but not `other`.
+    #[inline]
+    fn difference<S>(self, other: S) -> Combine<Self, Boolean<Self::Output, S>>
+    where
+        Boolean<Self::Output, S>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Boolean::new(other, BooleanOp::Difference),
+        }
+    }
+
+    /// Symmetric difference of self and `other`, the area covered by exactly one of them.
+    #[inline]
+    fn xor<S>(self, other: S) -> Combine<Self, Boolean<Self::Output, S>>
+    where
+        Boolean<Self::Output, S>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Boolean::new(other, BooleanOp::Xor),
+        }
+    }
+
+    /// Transforms the geometry by `affine`.
+    ///
+    /// Unlike [`ShapeExt::map`] this keeps all guarantees about polygons.
+    #[inline]
+    fn transform(self, affine: Affine2) -> Combine<Self, Transform<Self::Output>>
+    where
+        Transform<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Transform::new(affine),
+        }
+    }
+
+    /// Moves the geometry by `offset`.
+    #[inline]
+    fn translate(self, offset: impl Into<Vec2>) -> Combine<Self, Transform<Self::Output>>
+    where
+        Transform<Self::Output>: Shape,
+    {
+        self.transform(Affine2::from_translation(offset.into()))
+    }
+
+    /// Rotates the geometry counter clockwise around the origin by `angle` in radians.
+    #[inline]
+    fn rotate(self, angle: f32) -> Combine<Self, Transform<Self::Output>>
+    where
+        Transform<Self::Output>: Shape,
+    {
+        self.transform(Affine2::from_angle(angle))
+    }
+
+    /// Scales the geometry from the origin, negative scales mirror it.
+    #[inline]
+    fn scale(self, scale: impl Into<Vec2>) -> Combine<Self, Transform<Self::Output>>
+    where
+        Transform<Self::Output>: Shape,
+    {
+        self.transform(Affine2::from_scale(scale.into()))
+    }
+
+    /// Skews the geometry by angles `x` along the x axis and `y` along the y axis, in radians.
+    #[inline]
+    fn skew(self, x: f32, y: f32) -> Combine<Self, Transform<Self::Output>>
+    where
+        Transform<Self::Output>: Shape,
+    {
+        let matrix = Mat2::from_cols(Vec2::new(1.0, y.tan()), Vec2::new(x.tan(), 1.0));
+
+        self.transform(Affine2::from_mat2(matrix))
+    }
+
+    /// Rounds corners with arcs of `radius`, see [`RoundCorners`].
+    #[inline]
+    fn round_corners(self, radius: f32) -> Combine<Self, RoundCorners<Self::Output>>
+    where
+        RoundCorners<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: RoundCorners::new(radius),
+        }
+    }
+
+    /// Cuts corners off `distance` along both edges, see [`Chamfer`].
+    #[inline]
+    fn chamfer(self, distance: f32) -> Combine<Self, Chamfer<Self::Output>>
+    where
+        Chamfer<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Chamfer::new(distance),
+        }
+    }
+
+    /// Smooths corners by cutting them `iterations` times, see [`Smooth`].
+    #[inline]
+    fn smooth(self, iterations: usize) -> Combine<Self, Smooth<Self::Output>>
+    where
+        Smooth<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Smooth::new(iterations),
+        }
+    }
+
+    /// Spaces points evenly along the path, at most `spacing` apart, see [`Resample`].
+    #[inline]
+    fn resample(self, spacing: f32) -> Combine<Self, Resample<Self::Output>>
+    where
+        Resample<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Resample::new(spacing),
+        }
+    }
+
+    /// Splits the path into dashes alternating between the lengths in `pattern`, see [`Dash`].
+    #[inline]
+    fn dash(self, pattern: &[f32]) -> Combine<Self, Dash<Self::Output>>
+    where
+        Dash<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Dash::new(pattern),
+        }
+    }
+
+    /// Splits the path into dashes with the pattern and offset of `dash`, see [`Dash`].
+    #[inline]
+    fn dash_with(self, dash: Dash<Self::Output>) -> Combine<Self, Dash<Self::Output>>
+    where
+        Dash<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: dash,
+        }
+    }
+
+    /// Keeps the part of the path from `start` to `end` as fractions of its length, see [`Trim`].
+    #[inline]
+    fn trim(self, start: f32, end: f32) -> Combine<Self, Trim<Self::Output>>
+    where
+        Trim<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Trim::new(start, end),
+        }
+    }
+
+    /// Reduces the number of points using `algorithm`, without introducing intersections, see
+    /// [`Simplify`].
+    #[inline]
+    fn simplify(self, tolerance: f32, algorithm: Algorithm) -> Combine<Self, Simplify<Self::Output>>
+    where
+        Simplify<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Simplify::new(tolerance, algorithm),
+        }
+    }
+
     /// Runs closure for each point in a polygon.
     ///
     /// This breaks any guarantees about the polygon requiring a complete re-verification, thus
@@ -123,6 +395,80 @@ pub trait ShapeExt: Shape + Sized {
         }
     }
 
+    /// Sets the texture coordinates of a mesh.
+    #[inline]
+    fn uv(self, mapping: UvMapping) -> Combine<Self, Uv>
+    where
+        Self: Shape<Output = Mesh>,
+    {
+        Combine {
+            input: self,
+            output: Uv { mapping },
+        }
+    }
+
+    /// Maps texture coordinates along a line onto the mesh of the line thickened by
+    /// `thickness`, see [`StrokeUv`].
+    #[inline]
+    fn stroke_uv(self, thickness: f32) -> Combine<Self, StrokeUv>
+    where
+        Self: Shape<Output = (Mesh, Polyline)>,
+    {
+        Combine {
+            input: self,
+            output: StrokeUv { thickness },
+        }
+    }
+
+    /// Grows polygons by `distance`, or shrinks them if negative, see [`Inflate`].
+    #[inline]
+    fn inflate(self, distance: f32, join: LineJoin) -> Combine<Self, Inflate<Self::Output>>
+    where
+        Inflate<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Inflate::new(distance, join),
+        }
+    }
+
+    /// Smallest convex polygon containing all points, see [`ConvexHull`].
+    #[inline]
+    fn convex_hull(self) -> Combine<Self, ConvexHull<Self::Output>>
+    where
+        ConvexHull<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: ConvexHull::new(),
+        }
+    }
+
+    /// Splits polygons into convex pieces using [`Decomposition::HertelMehlhorn`].
+    #[inline]
+    fn convex_decompose(self) -> Combine<Self, ConvexDecompose<Self::Output>>
+    where
+        ConvexDecompose<Self::Output>: Shape,
+    {
+        self.convex_decompose_with(ConvexDecompose::new())
+    }
+
+    /// Splits polygons into convex pieces with the options of `decompose`, see
+    /// [`ConvexDecompose`].
+    #[inline]
+    fn convex_decompose_with(
+        self,
+        decompose: ConvexDecompose<Self::Output>,
+    ) -> Combine<Self, ConvexDecompose<Self::Output>>
+    where
+        ConvexDecompose<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: decompose,
+        }
+    }
+
     /// Outlines the shape.
     ///
     /// **Note** this is quite costly.
@@ -137,17 +483,50 @@ pub trait ShapeExt: Shape + Sized {
         }
     }
 
+    /// Outlines the shape with the join and antialiasing of `outline`, see [`Outline`].
+    ///
+    /// **Note** this is quite costly.
+    #[inline]
+    fn outline_with(self, outline: Outline<Self::Output>) -> Combine<Self, Outline<Self::Output>>
+    where
+        Outline<Self::Output>: Shape,
+    {
+        Combine {
+            input: self,
+            output: outline,
+        }
+    }
+
     /// Triangulates polygon, thus *filling* them.
     ///
+    /// `paint` can be a color, a gradient or a closure, see [`Paint`].
+    ///
     /// **Note** this is quite costly.
     #[inline]
-    fn fill(self, color: impl Into<[f32; 4]>) -> Combine<Self, Fill<Self::Output>>
+    fn fill<P: Paint>(self, paint: P) -> Combine<Self, Fill<Self::Output, P>>
+    where
+        Fill<Self::Output, P>: Shape,
+    {
+        Combine {
+            input: self,
+            output: Fill::new(paint),
+        }
+    }
+
+    /// Triangulates polygon with the options of `fill`, see [`Fill`].
+    ///
+    /// **Note** this is quite costly.
+    #[inline]
+    fn fill_with<P: Paint>(
+        self,
+        fill: Fill<Self::Output, P>,
+    ) -> Combine<Self, Fill<Self::Output, P>>
     where
-        Fill<Self::Output>: Shape,
+        Fill<Self::Output, P>: Shape,
     {
         Combine {
             input: self,
-            output: Fill::new(color.into()),
+            output: fill,
         }
     }
 }
@@ -1,4 +1,6 @@
-use crate::polygon::Polygon;
+use glam::{Affine2, Vec2};
+
+use crate::{error::Error, polygon::Polygon};
 
 /// A polygon with holes.
 #[derive(Clone, Debug)]
@@ -8,6 +10,89 @@ pub struct HoledPolygon {
 }
 
 impl HoledPolygon {
+    /// Transforms the polygon and its holes by `affine`, see [`Polygon::transform`].
+    #[inline]
+    pub fn transform(&mut self, affine: Affine2) {
+        self.polygon.transform(affine);
+
+        for hole in &mut self.holes {
+            hole.transform(affine);
+        }
+    }
+
+    /// Checks if `point` lies inside the polygon and outside every hole.
+    #[inline]
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.polygon.contains(point) && !self.holes.iter().any(|hole| hole.contains(point))
+    }
+
+    /// Area of the polygon minus that of the holes, with the sign of the polygon's area, see
+    /// [`Polygon::area`].
+    #[inline]
+    pub fn area(&self) -> f32 {
+        let area = self.polygon.area();
+        let holes: f32 = self.holes.iter().map(|hole| hole.area().abs()).sum();
+
+        (area.abs() - holes).copysign(area)
+    }
+
+    /// Center of mass taking holes into account, or `None` if the polygon is empty.
+    ///
+    /// Falls back to the centroid of the polygon if the holes cover all of it.
+    #[inline]
+    pub fn centroid(&self) -> Option<Vec2> {
+        let outer = self.polygon.centroid()?;
+
+        let mut area = self.polygon.area().abs();
+        let mut centroid = outer * area;
+
+        for hole in &self.holes {
+            let hole_area = hole.area().abs();
+
+            if let Some(hole_centroid) = hole.centroid() {
+                centroid -= hole_centroid * hole_area;
+                area -= hole_area;
+            }
+        }
+
+        if area <= self.polygon.area().abs() * 1e-6 {
+            Some(outer)
+        } else {
+            Some(centroid / area)
+        }
+    }
+
+    /// Length of the boundary, including that of the holes.
+    #[inline]
+    pub fn perimeter(&self) -> f32 {
+        self.polygon.perimeter() + self.holes.iter().map(Polygon::perimeter).sum::<f32>()
+    }
+
+    /// Axis aligned bounding box of the polygon, see [`Polygon::bounds`].
+    #[inline]
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        self.polygon.bounds()
+    }
+
+    /// Closest point on the boundary, including that of the holes, to `point`.
+    #[inline]
+    pub fn closest_point(&self, point: Vec2) -> Option<Vec2> {
+        std::iter::once(&self.polygon)
+            .chain(&self.holes)
+            .filter_map(|polygon| polygon.closest_point(point))
+            .min_by(|a, b| {
+                a.distance_squared(point)
+                    .total_cmp(&b.distance_squared(point))
+            })
+    }
+
+    /// Distance from `point` to the boundary, including that of the holes.
+    #[inline]
+    pub fn distance(&self, point: Vec2) -> f32 {
+        self.closest_point(point)
+            .map_or(f32::INFINITY, |closest| closest.distance(point))
+    }
+
     #[inline]
     pub fn verify(&mut self) {
         self.polygon.verify();
@@ -16,6 +101,18 @@ impl HoledPolygon {
             hole.verify();
         }
     }
+
+    /// See [`Polygon::try_verify`].
+    #[inline]
+    pub fn try_verify(&mut self) -> Result<(), Error> {
+        self.polygon.try_verify()?;
+
+        for hole in &mut self.holes {
+            hole.try_verify()?;
+        }
+
+        Ok(())
+    }
 }
 
 impl From<Polygon> for HoledPolygon {
@@ -26,3 +123,39 @@ impl From<Polygon> for HoledPolygon {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(min: f32, max: f32) -> Polygon {
+        Polygon::from(vec![
+            Vec2::new(min, min),
+            Vec2::new(max, min),
+            Vec2::new(max, max),
+            Vec2::new(min, max),
+        ])
+    }
+
+    #[test]
+    fn queries() {
+        let mut polygon = HoledPolygon::from(square(0.0, 4.0));
+        polygon.holes.push(square(2.0, 4.0));
+
+        assert_eq!(polygon.area(), 12.0);
+        assert_eq!(polygon.perimeter(), 24.0);
+
+        // the three remaining quarters
+        assert!(polygon.centroid().unwrap().distance(Vec2::splat(5.0 / 3.0)) < 1e-6);
+
+        assert!(polygon.contains(Vec2::ONE));
+        assert!(!polygon.contains(Vec2::splat(3.0)));
+        assert_eq!(polygon.distance(Vec2::new(2.5, 3.0)), 0.5);
+
+        // holes covering all of it
+        polygon.holes[0] = square(0.0, 4.0);
+        assert_eq!(polygon.centroid(), Some(Vec2::splat(2.0)));
+
+        assert_eq!(HoledPolygon::from(Polygon::default()).centroid(), None);
+    }
+}
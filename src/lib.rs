@@ -3,6 +3,8 @@
 //! # Example
 //! This is the code used to create the logo.
 //! ```
+//! # use std::f32::consts::{PI, SQRT_2, TAU};
+//! # use scissor::prelude::*;
 //! // create curve that starts at the bottom, we do this to ensure a vertex is placed
 //! // at the point
 //! let shape = Parametric::new(|x| Vec2::new(x.sin(), -x.cos()), 0.0..TAU)
@@ -31,26 +33,38 @@
 //!
 #![doc(html_logo_url = "https://raw.githubusercontent.com/ChangeCaps/scissor/main/logo.png")]
 
+mod boolean;
+mod decompose;
+mod delaunay;
+mod error;
 mod ext;
 mod holed_polygon;
 pub mod mesh;
+mod paint;
+mod path_measure;
 mod polygon;
 mod polyline;
 mod shape;
 pub mod shapes;
+mod simplify;
+pub mod svg;
 
+pub use error::Error;
 pub use ext::ShapeExt;
 #[doc(hidden)]
 pub use glam;
 pub use holed_polygon::HoledPolygon;
+pub use paint::{LinearGradient, Paint, RadialGradient};
+pub use path_measure::PathMeasure;
 pub use polygon::Polygon;
 pub use polyline::Polyline;
-pub use shape::{Config, Shape};
+pub use shape::{Config, Shape, Tolerance};
 #[doc(hidden)]
 pub use shapes::*;
 
 pub mod prelude {
     pub use crate::ext::*;
+    pub use crate::paint::*;
     pub use crate::shape::Shape;
     pub use crate::shapes::*;
 
@@ -1,6 +1,4 @@
-use glam::Vec3;
-
-use crate::Shape;
+use glam::{Affine2, Vec2, Vec3};
 
 /// Vertex in a [`Mesh`].
 ///
@@ -10,6 +8,9 @@ use crate::Shape;
 pub struct Vertex {
     pub position: Vec3,
     pub color: [f32; 4],
+    /// Texture coordinates, see [`Uv`](crate::shapes::Uv) and
+    /// [`StrokeUv`](crate::shapes::StrokeUv).
+    pub uv: Vec2,
 }
 
 #[cfg(feature = "bytemuck")]
@@ -23,4 +24,47 @@ unsafe impl bytemuck::Zeroable for Vertex {}
 pub struct Mesh {
     pub vertices: Vec<Vertex>,
     pub indices: Vec<u32>,
-}
\ No newline at end of file
+}
+
+impl Mesh {
+    /// Checks if `point` lies inside any triangle, ignoring depth.
+    #[inline]
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.indices.chunks_exact(3).any(|triangle| {
+            let a = self.vertices[triangle[0] as usize].position.truncate();
+            let b = self.vertices[triangle[1] as usize].position.truncate();
+            let c = self.vertices[triangle[2] as usize].position.truncate();
+
+            let d0 = (b - a).perp_dot(point - a);
+            let d1 = (c - b).perp_dot(point - b);
+            let d2 = (a - c).perp_dot(point - c);
+
+            // inside if on the same side of every edge, regardless of winding
+            (d0 >= 0.0 && d1 >= 0.0 && d2 >= 0.0) || (d0 <= 0.0 && d1 <= 0.0 && d2 <= 0.0)
+        })
+    }
+
+    /// Checks that the position of every vertex is finite.
+    #[inline]
+    pub(crate) fn is_finite(&self) -> bool {
+        self.vertices.iter().all(|v| v.position.is_finite())
+    }
+
+    /// Transforms the position of every vertex by `affine`, keeping the depth.
+    ///
+    /// If `affine` mirrors the mesh, the triangles are reversed to keep their winding order.
+    #[inline]
+    pub fn transform(&mut self, affine: Affine2) {
+        for vertex in &mut self.vertices {
+            let position = affine.transform_point2(vertex.position.truncate());
+
+            vertex.position = position.extend(vertex.position.z);
+        }
+
+        if affine.matrix2.determinant() < 0.0 {
+            for triangle in self.indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use glam::{Vec2, Vec4};
+
+/// Determines the color at every vertex of a [`Fill`](crate::shapes::Fill).
+///
+/// Implemented for solid colors, gradients and closures `Fn(Vec2) -> [f32; 4]`.
+pub trait Paint {
+    fn color(&self, point: Vec2) -> [f32; 4];
+
+    /// Whether the color is the same everywhere, in which case there is no need to refine
+    /// triangles.
+    #[inline]
+    fn is_solid(&self) -> bool {
+        false
+    }
+}
+
+impl Paint for [f32; 4] {
+    #[inline]
+    fn color(&self, _point: Vec2) -> [f32; 4] {
+        *self
+    }
+
+    #[inline]
+    fn is_solid(&self) -> bool {
+        true
+    }
+}
+
+impl Paint for Vec4 {
+    #[inline]
+    fn color(&self, _point: Vec2) -> [f32; 4] {
+        (*self).into()
+    }
+
+    #[inline]
+    fn is_solid(&self) -> bool {
+        true
+    }
+}
+
+impl<F: Fn(Vec2) -> [f32; 4]> Paint for F {
+    #[inline]
+    fn color(&self, point: Vec2) -> [f32; 4] {
+        self(point)
+    }
+}
+
+/// Evaluates gradient `stops` at `t`, stops **must** be sorted by offset.
+#[inline]
+fn gradient(stops: &[(f32, [f32; 4])], t: f32) -> [f32; 4] {
+    let i = stops.iter().position(|(offset, _)| *offset > t);
+
+    match i {
+        None => stops.last().map_or([0.0; 4], |(_, color)| *color),
+        Some(0) => stops[0].1,
+        Some(i) => {
+            let (o0, c0) = stops[i - 1];
+            let (o1, c1) = stops[i];
+
+            let l = (t - o0) / (o1 - o0);
+
+            Vec4::from(c0).lerp(Vec4::from(c1), l).into()
+        }
+    }
+}
+
+/// Inserts a stop keeping the stops sorted.
+#[inline]
+fn insert_stop(stops: &mut Vec<(f32, [f32; 4])>, offset: f32, color: [f32; 4]) {
+    let i = stops
+        .iter()
+        .position(|(o, _)| *o > offset)
+        .unwrap_or(stops.len());
+
+    stops.insert(i, (offset, color));
+}
+
+/// Gradient along the line from `start` to `end`.
+///
+/// # Example
+/// ```
+/// # use scissor::{prelude::*, LinearGradient};
+/// let gradient = LinearGradient::new(Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0))
+///     .stop(0.0, [1.0, 0.0, 0.0, 1.0])
+///     .stop(1.0, [0.0, 0.0, 1.0, 1.0]);
+///
+/// let shape = Circle::new(1.0).fill(gradient);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearGradient {
+    pub start: Vec2,
+    pub end: Vec2,
+    /// Offsets and colors, sorted by offset.
+    pub stops: Vec<(f32, [f32; 4])>,
+}
+
+impl LinearGradient {
+    #[inline]
+    pub fn new(start: impl Into<Vec2>, end: impl Into<Vec2>) -> Self {
+        Self {
+            start: start.into(),
+            end: end.into(),
+            stops: Vec::new(),
+        }
+    }
+
+    /// Adds a color stop, where `offset` 0 is at `start` and 1 at `end`.
+    #[inline]
+    pub fn stop(mut self, offset: f32, color: impl Into<[f32; 4]>) -> Self {
+        insert_stop(&mut self.stops, offset, color.into());
+        self
+    }
+}
+
+impl Paint for LinearGradient {
+    #[inline]
+    fn color(&self, point: Vec2) -> [f32; 4] {
+        let d = self.end - self.start;
+        let t = (point - self.start).dot(d) / d.length_squared();
+
+        gradient(&self.stops, t)
+    }
+}
+
+/// Gradient from `center` out to `radius`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadialGradient {
+    pub center: Vec2,
+    pub radius: f32,
+    /// Offsets and colors, sorted by offset.
+    pub stops: Vec<(f32, [f32; 4])>,
+}
+
+impl RadialGradient {
+    #[inline]
+    pub fn new(center: impl Into<Vec2>, radius: f32) -> Self {
+        Self {
+            center: center.into(),
+            radius,
+            stops: Vec::new(),
+        }
+    }
+
+    /// Adds a color stop, where `offset` 0 is at `center` and 1 at `radius`.
+    #[inline]
+    pub fn stop(mut self, offset: f32, color: impl Into<[f32; 4]>) -> Self {
+        insert_stop(&mut self.stops, offset, color.into());
+        self
+    }
+}
+
+impl Paint for RadialGradient {
+    #[inline]
+    fn color(&self, point: Vec2) -> [f32; 4] {
+        gradient(&self.stops, point.distance(self.center) / self.radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+    const BLUE: [f32; 4] = [0.0, 0.0, 1.0, 1.0];
+
+    #[test]
+    fn solid() {
+        assert_eq!(RED.color(Vec2::ONE), RED);
+        assert_eq!(Vec4::from(RED).color(Vec2::ONE), RED);
+        assert!(RED.is_solid());
+
+        let paint = |p: Vec2| [p.x, p.y, 0.0, 1.0];
+        assert_eq!(paint.color(Vec2::new(0.5, 0.25)), [0.5, 0.25, 0.0, 1.0]);
+        assert!(!paint.is_solid());
+    }
+
+    #[test]
+    fn linear() {
+        // stops added out of order
+        let gradient = LinearGradient::new((1.0, 0.0), (3.0, 0.0))
+            .stop(1.0, BLUE)
+            .stop(0.0, RED);
+
+        assert_eq!(gradient.stops, vec![(0.0, RED), (1.0, BLUE)]);
+        assert!(!gradient.is_solid());
+
+        assert_eq!(gradient.color(Vec2::new(1.0, 5.0)), RED);
+        assert_eq!(gradient.color(Vec2::new(2.0, -5.0)), [0.5, 0.0, 0.5, 1.0]);
+        assert_eq!(gradient.color(Vec2::new(3.0, 0.0)), BLUE);
+
+        // clamped past both ends
+        assert_eq!(gradient.color(Vec2::new(-1.0, 0.0)), RED);
+        assert_eq!(gradient.color(Vec2::new(5.0, 0.0)), BLUE);
+
+        // no stops
+        let empty = LinearGradient::new(Vec2::ZERO, Vec2::X);
+        assert_eq!(empty.color(Vec2::ZERO), [0.0; 4]);
+    }
+
+    #[test]
+    fn radial() {
+        let gradient = RadialGradient::new(Vec2::ONE, 2.0)
+            .stop(0.0, RED)
+            .stop(0.5, BLUE)
+            .stop(1.0, [0.0; 4]);
+
+        assert_eq!(gradient.color(Vec2::ONE), RED);
+        assert_eq!(gradient.color(Vec2::new(1.0, 1.5)), [0.5, 0.0, 0.5, 1.0]);
+        assert_eq!(gradient.color(Vec2::new(-1.0, 1.0)), [0.0; 4]);
+        assert_eq!(gradient.color(Vec2::new(1.0, 2.5)), [0.0, 0.0, 0.5, 0.5]);
+    }
+}
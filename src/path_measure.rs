@@ -0,0 +1,300 @@
+use glam::Vec2;
+
+use crate::{polygon::Polygon, polyline::Polyline};
+
+/// Largest number of points a path is resampled into.
+const MAX_SAMPLES: usize = 1 << 20;
+
+/// Measures distances along a [`Polyline`] or the boundary of a [`Polygon`].
+///
+/// Useful for animating along paths, distances are clamped to the path for lines and wrap
+/// around for polygons.
+#[derive(Clone, Debug, Default)]
+pub struct PathMeasure {
+    points: Vec<Vec2>,
+    /// Distance along the path at each point.
+    distances: Vec<f32>,
+    closed: bool,
+}
+
+impl PathMeasure {
+    /// Measures the path through `points`, connecting the last point to the first if `closed`.
+    #[inline]
+    pub fn new(points: &[Vec2], closed: bool) -> Self {
+        let mut points = points.to_vec();
+        points.dedup();
+
+        if closed && points.len() > 1 {
+            // an explicitly closed ring would otherwise end in a segment without length
+            if points[0] == points[points.len() - 1] {
+                points.pop();
+            }
+
+            points.push(points[0]);
+        }
+
+        let mut distances = Vec::with_capacity(points.len());
+        let mut distance = 0.0;
+
+        for i in 0..points.len() {
+            if i > 0 {
+                distance += points[i - 1].distance(points[i]);
+            }
+
+            distances.push(distance);
+        }
+
+        Self {
+            points,
+            distances,
+            closed,
+        }
+    }
+
+    /// Total length of the path.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// Index of the segment containing `distance` and the distance along it.
+    #[inline]
+    fn segment(&self, distance: f32) -> Option<(usize, f32)> {
+        if self.points.len() < 2 {
+            return None;
+        }
+
+        let length = self.length();
+
+        let distance = if self.closed && length > 0.0 {
+            distance.rem_euclid(length)
+        } else {
+            distance.max(0.0).min(length)
+        };
+
+        let i = self
+            .distances
+            .partition_point(|d| *d <= distance)
+            .max(1)
+            .min(self.points.len() - 1)
+            - 1;
+
+        Some((i, distance - self.distances[i]))
+    }
+
+    /// Point at `distance` along the path.
+    ///
+    /// # Panics
+    /// If the path is empty.
+    #[inline]
+    pub fn point(&self, distance: f32) -> Vec2 {
+        match self.segment(distance) {
+            Some((i, d)) => {
+                let (p0, p1) = (self.points[i], self.points[i + 1]);
+
+                p0 + (p1 - p0).normalize() * d
+            }
+            None => self.points[0],
+        }
+    }
+
+    /// Normalized direction of the path at `distance`, zero if the path has no length.
+    #[inline]
+    pub fn tangent(&self, distance: f32) -> Vec2 {
+        match self.segment(distance) {
+            Some((i, _)) => (self.points[i + 1] - self.points[i]).normalize(),
+            None => Vec2::ZERO,
+        }
+    }
+
+    /// Point at `t` along the path, where 0 is the start and 1 the end.
+    ///
+    /// # Panics
+    /// If the path is empty.
+    #[inline]
+    pub fn point_at(&self, t: f32) -> Vec2 {
+        self.point(t * self.length())
+    }
+
+    /// Direction of the path at `t`, where 0 is the start and 1 the end.
+    #[inline]
+    pub fn tangent_at(&self, t: f32) -> Vec2 {
+        self.tangent(t * self.length())
+    }
+
+    /// Points of the path from distance `start` to `end`, with both ends interpolated.
+    ///
+    /// For polygons the range wraps around the first point and an `end` before `start` is
+    /// reached by going around once, for lines both distances are clamped to the path.
+    ///
+    /// # Panics
+    /// If the path is empty.
+    pub fn extract(&self, start: f32, end: f32) -> Vec<Vec2> {
+        let (mut i, d) = match self.segment(start) {
+            Some(segment) => segment,
+            None => return vec![self.points[0]],
+        };
+
+        let length = self.length();
+        let span = end - start;
+        let start = self.distances[i] + d;
+
+        let end = if self.closed {
+            let span = if span < 0.0 {
+                span.rem_euclid(length)
+            } else {
+                span
+            };
+
+            start + span.min(length)
+        } else {
+            end.max(start).min(length)
+        };
+
+        let mut points = vec![self.point(start)];
+        let mut offset = 0.0;
+
+        loop {
+            i += 1;
+
+            if i == self.points.len() {
+                if !self.closed {
+                    break;
+                }
+
+                // the last point repeats the first
+                i = 1;
+                offset += length;
+            }
+
+            if self.distances[i] + offset >= end {
+                break;
+            }
+
+            points.push(self.points[i]);
+        }
+
+        points.push(self.point(end));
+        points.dedup();
+
+        points
+    }
+
+    /// Points spaced evenly along the path, at most `spacing` apart.
+    ///
+    /// Lines keep both end points, polygons start at their first point and don't repeat it.
+    /// There are never more than 2<sup>20</sup> steps, regardless of `spacing`.
+    #[inline]
+    pub fn resample(&self, spacing: f32) -> Vec<Vec2> {
+        if self.points.len() < 2 {
+            return self.points.clone();
+        }
+
+        let length = self.length();
+        let min = if self.closed { 3 } else { 1 };
+        let steps = ((length / spacing).ceil() as usize)
+            .min(MAX_SAMPLES)
+            .max(min);
+
+        let mut points: Vec<Vec2> = (0..steps)
+            .map(|i| self.point(length * i as f32 / steps as f32))
+            .collect();
+
+        if !self.closed {
+            points.push(self.points[self.points.len() - 1]);
+        }
+
+        points
+    }
+}
+
+impl From<&Polyline> for PathMeasure {
+    #[inline]
+    fn from(polyline: &Polyline) -> Self {
+        Self::new(&polyline.points, false)
+    }
+}
+
+impl From<&Polygon> for PathMeasure {
+    #[inline]
+    fn from(polygon: &Polygon) -> Self {
+        Self::new(&polygon.points, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2::ZERO,
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ]
+    }
+
+    #[test]
+    fn point_and_tangent() {
+        let line = PathMeasure::new(&square(), false);
+        let ring = PathMeasure::new(&square(), true);
+
+        assert_eq!(line.length(), 3.0);
+        assert_eq!(ring.length(), 4.0);
+
+        assert_eq!(line.point(1.5), Vec2::new(1.0, 0.5));
+        assert_eq!(line.tangent(1.5), Vec2::Y);
+
+        // lines clamp, polygons wrap around
+        assert_eq!(line.point(-1.0), Vec2::ZERO);
+        assert_eq!(line.point(5.0), Vec2::new(0.0, 1.0));
+        assert_eq!(ring.point(4.5), Vec2::new(0.5, 0.0));
+        assert_eq!(ring.point(-0.5), Vec2::new(0.0, 0.5));
+        assert_eq!(ring.tangent(3.5), -Vec2::Y);
+
+        assert_eq!(ring.point_at(0.5), Vec2::ONE);
+    }
+
+    #[test]
+    fn extract() {
+        let line = PathMeasure::new(&square(), false);
+        let ring = PathMeasure::new(&square(), true);
+
+        assert_eq!(
+            line.extract(0.5, 1.5),
+            vec![
+                Vec2::new(0.5, 0.0),
+                Vec2::new(1.0, 0.0),
+                Vec2::new(1.0, 0.5)
+            ]
+        );
+
+        // an end before the start is clamped on lines
+        assert_eq!(line.extract(1.5, 0.5), vec![Vec2::new(1.0, 0.5)]);
+
+        // and goes around on polygons
+        assert_eq!(
+            ring.extract(3.5, 0.5),
+            vec![Vec2::new(0.0, 0.5), Vec2::ZERO, Vec2::new(0.5, 0.0)]
+        );
+        assert_eq!(ring.extract(0.5, 4.5).len(), 6);
+    }
+
+    #[test]
+    fn resample() {
+        let line = PathMeasure::new(&square(), false);
+        let ring = PathMeasure::new(&square(), true);
+
+        let points = line.resample(0.5);
+        assert_eq!(points.len(), 7);
+        assert_eq!(points[6], Vec2::new(0.0, 1.0));
+
+        let points = ring.resample(0.5);
+        assert_eq!(points.len(), 8);
+        assert_eq!(points[7], Vec2::new(0.0, 0.5));
+
+        // the number of points is bounded
+        assert_eq!(ring.resample(1e-30).len(), MAX_SAMPLES);
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use glam::Vec2;
+use glam::{Affine2, Vec2};
+
+use crate::{error::Error, path_measure::PathMeasure};
 
 /// Polygon defined by a list of lines.
 #[derive(Clone, Debug, Default)]
@@ -32,6 +34,28 @@ impl Polygon {
         self.points.push(point);
     }
 
+    /// Transforms every point by `affine`.
+    ///
+    /// Guarantees about the polygon are kept, flipping the winding order if `affine` mirrors
+    /// the polygon.
+    #[inline]
+    pub fn transform(&mut self, affine: Affine2) {
+        for point in &mut self.points {
+            *point = affine.transform_point2(*point);
+        }
+
+        let determinant = affine.matrix2.determinant();
+
+        if determinant < 0.0 {
+            self.is_ccw = self.is_ccw.map(|is_ccw| !is_ccw);
+        } else if determinant == 0.0 {
+            // collapsed onto a line
+            self.is_ccw = None;
+            self.is_convex = None;
+            self.is_simple = false;
+        }
+    }
+
     /// Removes all self intersections.
     #[inline]
     pub fn remove_intersection(&mut self) {
@@ -45,7 +69,7 @@ impl Polygon {
                 continue;
             }
 
-            if let Some(e) = intersections.into_iter().rev().next() {
+            if let Some(e) = intersections.into_iter().next_back() {
                 if i >= e {
                     continue;
                 }
@@ -99,33 +123,38 @@ impl Polygon {
 
         impl Ord for Node2 {
             fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-                self.partial_cmp(&other).unwrap()
+                if self.0.y == other.0.y {
+                    self.0.x.partial_cmp(&other.0.x).unwrap()
+                } else {
+                    self.0.y.partial_cmp(&other.0.y).unwrap()
+                }
             }
         }
 
         impl PartialOrd for Node2 {
             fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-                if self.0.y == other.0.y {
-                    self.0.x.partial_cmp(&other.0.x)
-                } else {
-                    self.0.y.partial_cmp(&other.0.y)
-                }
+                Some(self.cmp(other))
             }
         }
 
-        #[derive(Debug, PartialEq, PartialOrd)]
+        #[derive(Debug, PartialEq)]
         struct Node(f32);
 
         impl Eq for Node {}
 
         impl Ord for Node {
             fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-                self.partial_cmp(&other).unwrap()
+                self.0.partial_cmp(&other.0).unwrap()
+            }
+        }
+
+        impl PartialOrd for Node {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
             }
         }
 
         let events: BTreeMap<_, _> = (0..self.points.len())
-            .into_iter()
             .map(|i| (Node2(self.points[i]), i))
             .collect();
 
@@ -240,6 +269,112 @@ impl Polygon {
         }
     }
 
+    /// Checks if `point` lies inside self, using the even-odd rule.
+    #[inline]
+    pub fn contains(&self, point: Vec2) -> bool {
+        let mut inside = false;
+
+        for i in 0..self.points.len() {
+            let p0 = self.points[i];
+            let p1 = self.points[(i + 1) % self.points.len()];
+
+            if (p0.y > point.y) != (p1.y > point.y) {
+                let x = p0.x + (point.y - p0.y) / (p1.y - p0.y) * (p1.x - p0.x);
+
+                if point.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+
+        inside
+    }
+
+    /// Signed area, positive if counter clockwise.
+    #[inline]
+    pub fn area(&self) -> f32 {
+        let mut area = 0.0;
+
+        for i in 0..self.points.len() {
+            area += self.points[i].perp_dot(self.points[(i + 1) % self.points.len()]);
+        }
+
+        area / 2.0
+    }
+
+    /// Center of mass, or `None` if self is empty.
+    ///
+    /// Falls back to the average of the points if self has no area, such as when all points
+    /// lie on a line.
+    #[inline]
+    pub fn centroid(&self) -> Option<Vec2> {
+        if self.points.is_empty() {
+            return None;
+        }
+
+        let mut centroid = Vec2::ZERO;
+        let mut area = 0.0;
+        let mut total = 0.0;
+
+        for i in 0..self.points.len() {
+            let p0 = self.points[i];
+            let p1 = self.points[(i + 1) % self.points.len()];
+
+            let cross = p0.perp_dot(p1);
+
+            centroid += (p0 + p1) * cross;
+            area += cross;
+            total += cross.abs();
+        }
+
+        // the area of collinear points cancels out to rounding errors rather than zero
+        if area.abs() <= total * 1e-6 {
+            Some(mean(&self.points))
+        } else {
+            Some(centroid / (area * 3.0))
+        }
+    }
+
+    /// Length of the boundary.
+    #[inline]
+    pub fn perimeter(&self) -> f32 {
+        (0..self.points.len())
+            .map(|i| self.points[i].distance(self.points[(i + 1) % self.points.len()]))
+            .sum()
+    }
+
+    /// Axis aligned bounding box as `(min, max)`, min is greater than max if self is empty.
+    #[inline]
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        bounds(&self.points)
+    }
+
+    /// Measures distances along the boundary, see [`PathMeasure`].
+    #[inline]
+    pub fn measure(&self) -> PathMeasure {
+        PathMeasure::from(self)
+    }
+
+    /// Closest point on the boundary to `point`, or `None` if self is empty.
+    #[inline]
+    pub fn closest_point(&self, point: Vec2) -> Option<Vec2> {
+        let len = self.points.len();
+
+        closest_point(
+            (0..len).map(|i| (self.points[i], self.points[(i + 1) % len])),
+            point,
+        )
+    }
+
+    /// Distance from `point` to the boundary, infinite if self is empty.
+    ///
+    /// Use [`Polygon::contains`] to check if the point is inside.
+    #[inline]
+    pub fn distance(&self, point: Vec2) -> f32 {
+        self.closest_point(point)
+            .map_or(f32::INFINITY, |closest| closest.distance(point))
+    }
+
     /// Insures that self is both simple and ccw.
     #[inline]
     pub fn verify(&mut self) {
@@ -250,8 +385,36 @@ impl Polygon {
         }
     }
 
+    /// Insures that self is both simple and ccw, returning an error if self has non finite
+    /// coordinates or less than three points left after cleaning.
+    #[inline]
+    pub fn try_verify(&mut self) -> Result<(), Error> {
+        crate::error::finite(&self.points)?;
+
+        self.verify();
+
+        if self.points.len() < 3 {
+            return Err(Error::Degenerate("polygon must have at least three points"));
+        }
+
+        Ok(())
+    }
+
+    /// Merges hole with self, by creating seamless connection.
+    ///
+    /// # Panics
+    /// If the hole isn't inside self, see [`Polygon::try_merge_hole`].
+    #[inline]
+    pub fn merge_hole(&mut self, hole: Polygon) {
+        self.try_merge_hole(hole).unwrap_or_else(|err| panic!("{}", err));
+    }
+
     /// Merges hole with self, by creating seamless connection.
-    pub fn merge_hole(&mut self, mut hole: Polygon) {
+    pub fn try_merge_hole(&mut self, mut hole: Polygon) -> Result<(), Error> {
+        if hole.points.len() < 3 {
+            return Err(Error::Degenerate("hole must have at least three points"));
+        }
+
         let (i, y) = hole
             .points
             .iter()
@@ -272,7 +435,7 @@ impl Polygon {
                 }
             })
             .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
-            .unwrap();
+            .ok_or(Error::HoleOutsidePolygon)?;
 
         hole.points.rotate_left(i);
         hole.points.push(hole.points[0]);
@@ -282,6 +445,8 @@ impl Polygon {
         for point in hole.points {
             self.points.insert(p + 1, point);
         }
+
+        Ok(())
     }
 
     /// Triangulates the polygon using optimized ear clipping.
@@ -289,10 +454,19 @@ impl Polygon {
     /// # Requirements
     /// 1. **Must** contain three or more points.
     /// 2. **Must** be counter clockwise winding order.
+    ///
+    /// # Panics
+    /// If the requirements aren't met, see [`Polygon::try_triangulate`].
     #[inline]
     pub fn triangulate(&self) -> Vec<usize> {
+        self.try_triangulate().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Triangulates the polygon using optimized ear clipping, returning an error if the
+    /// requirements of [`Polygon::triangulate`] aren't met.
+    pub fn try_triangulate(&self) -> Result<Vec<usize>, Error> {
         #[inline]
-        fn is_convex(points: &Vec<Vec2>, relations: &Vec<(usize, usize)>, i: usize) -> bool {
+        fn is_convex(points: &[Vec2], relations: &[(usize, usize)], i: usize) -> bool {
             let (prev, next) = relations[i];
 
             let p0 = points[prev];
@@ -304,8 +478,8 @@ impl Polygon {
 
         #[inline]
         fn is_ear(
-            points: &Vec<Vec2>,
-            relations: &Vec<(usize, usize)>,
+            points: &[Vec2],
+            relations: &[(usize, usize)],
             reflect: &BTreeSet<usize>,
             i: usize,
         ) -> bool {
@@ -344,8 +518,8 @@ impl Polygon {
 
         #[inline]
         fn reconfigure(
-            points: &Vec<Vec2>,
-            relations: &Vec<(usize, usize)>,
+            points: &[Vec2],
+            relations: &[(usize, usize)],
             convex: &mut BTreeSet<usize>,
             reflect: &mut BTreeSet<usize>,
             ears: &mut BTreeSet<usize>,
@@ -356,7 +530,7 @@ impl Polygon {
                     reflect.remove(&i);
                     convex.insert(i);
 
-                    if is_ear(points, relations, &reflect, i) {
+                    if is_ear(points, relations, reflect, i) {
                         ears.insert(i);
                     }
                 }
@@ -371,8 +545,13 @@ impl Polygon {
             }
         }
 
-        let mut relations = (0..self.points.len())
-            .into_iter()
+        if self.points.len() < 3 {
+            return Err(Error::Degenerate("polygon must have at least three points"));
+        }
+
+        crate::error::finite(&self.points)?;
+
+        let mut relations: Vec<_> = (0..self.points.len())
             .map(|i| {
                 (
                     (i + 1) % self.points.len(),
@@ -407,8 +586,7 @@ impl Polygon {
             let ear = if let Some(ear) = ears.iter().next() {
                 *ear
             } else {
-                println!("{:?}", reflect);
-                panic!("could not find ear, triangulation failed, this should not happen, please open an issue")
+                return Err(Error::Triangulation);
             };
 
             let (prev, next) = relations[ear];
@@ -445,8 +623,49 @@ impl Polygon {
             );
         }
 
-        indices
+        Ok(indices)
+    }
+}
+
+/// Axis aligned bounding box of `points` as `(min, max)`.
+#[inline]
+pub(crate) fn bounds(points: &[Vec2]) -> (Vec2, Vec2) {
+    points.iter().fold(
+        (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+        |(min, max), p| (min.min(*p), max.max(*p)),
+    )
+}
+
+/// Average of `points`.
+#[inline]
+pub(crate) fn mean(points: &[Vec2]) -> Vec2 {
+    points.iter().fold(Vec2::ZERO, |sum, p| sum + *p) / points.len() as f32
+}
+
+/// Closest point to `point` on the segment from `a` to `b`.
+#[inline]
+pub(crate) fn closest_point_on_segment(a: Vec2, b: Vec2, point: Vec2) -> Vec2 {
+    let d = b - a;
+
+    if d == Vec2::ZERO {
+        return a;
     }
+
+    a + d * ((point - a).dot(d) / d.length_squared()).clamp(0.0, 1.0)
+}
+
+/// Closest point to `point` on any of the `segments`.
+#[inline]
+pub(crate) fn closest_point(
+    segments: impl Iterator<Item = (Vec2, Vec2)>,
+    point: Vec2,
+) -> Option<Vec2> {
+    segments
+        .map(|(a, b)| closest_point_on_segment(a, b, point))
+        .min_by(|a, b| {
+            a.distance_squared(point)
+                .total_cmp(&b.distance_squared(point))
+        })
 }
 
 impl From<Vec<Vec2>> for Polygon {
@@ -460,3 +679,53 @@ impl From<Vec<Vec2>> for Polygon {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Polygon {
+        Polygon::from(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ])
+    }
+
+    #[test]
+    fn queries() {
+        let mut square = square();
+
+        assert_eq!(square.area(), 4.0);
+        assert_eq!(square.perimeter(), 8.0);
+        assert_eq!(square.centroid(), Some(Vec2::ONE));
+        assert_eq!(square.bounds(), (Vec2::ZERO, Vec2::splat(2.0)));
+
+        assert_eq!(square.closest_point(Vec2::new(1.0, -1.0)), Some(Vec2::X));
+        assert_eq!(square.distance(Vec2::new(3.0, 1.0)), 1.0);
+        assert_eq!(square.distance(Vec2::new(1.0, 0.5)), 0.5);
+
+        square.points.reverse();
+        assert_eq!(square.area(), -4.0);
+        assert_eq!(square.centroid(), Some(Vec2::ONE));
+    }
+
+    #[test]
+    fn degenerate() {
+        let empty = Polygon::default();
+
+        assert_eq!(empty.centroid(), None);
+        assert_eq!(empty.closest_point(Vec2::ZERO), None);
+        assert_eq!(empty.distance(Vec2::ZERO), f32::INFINITY);
+
+        // no area, the points are averaged
+        let line = Polygon::from(vec![Vec2::ZERO, Vec2::new(0.1, 0.3), Vec2::new(0.3, 0.9)]);
+        let centroid = line.centroid().unwrap();
+
+        assert!(centroid.distance(Vec2::new(0.4, 1.2) / 3.0) < 1e-6);
+
+        // a point that isn't a number has no meaningful distance, but doesn't panic
+        assert!(square().distance(Vec2::new(f32::NAN, 0.0)).is_nan());
+    }
+}
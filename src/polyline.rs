@@ -1,4 +1,6 @@
-use glam::Vec2;
+use glam::{Affine2, Vec2};
+
+use crate::{error::Error, path_measure::PathMeasure, polygon};
 
 /// Multiple lines connected.
 ///
@@ -16,15 +18,180 @@ impl Polyline {
         self.direction = None;
     }
 
+    /// Appends a quadratic bézier curve starting at the last point, or at the origin if the
+    /// polyline is empty.
+    ///
+    /// The curve is flattened such that it never deviates more than `tolerance` from the true
+    /// curve, and [`Polyline::direction`] is set to the tangent at `end`.
+    #[inline]
+    pub fn quad_to(&mut self, ctrl: Vec2, end: Vec2, tolerance: f32) {
+        if self.points.is_empty() {
+            self.points.push(Vec2::ZERO);
+        }
+
+        let p0 = self.points[self.points.len() - 1];
+
+        // a quadratic curve is just a cubic with both control points on the same tangents
+        let c1 = p0 + (ctrl - p0) * (2.0 / 3.0);
+        let c2 = end + (ctrl - end) * (2.0 / 3.0);
+
+        self.cubic_to(c1, c2, end, tolerance);
+    }
+
+    /// Appends a cubic bézier curve starting at the last point, or at the origin if the
+    /// polyline is empty.
+    ///
+    /// The curve is flattened such that it never deviates more than `tolerance` from the true
+    /// curve, and [`Polyline::direction`] is set to the tangent at `end`.
+    pub fn cubic_to(&mut self, c1: Vec2, c2: Vec2, end: Vec2, tolerance: f32) {
+        fn flatten(points: &mut Vec<Vec2>, p: [Vec2; 4], tolerance: f32, depth: u32) {
+            // distance to the chord itself rather than the line through it, catching control
+            // points that are collinear but overshoot its ends
+            let distance = |c: Vec2| polygon::closest_point_on_segment(p[0], p[3], c).distance(c);
+
+            if depth == 0 || distance(p[1]).max(distance(p[2])) <= tolerance {
+                points.push(p[3]);
+                return;
+            }
+
+            // de casteljau subdivision at the midpoint
+            let p01 = (p[0] + p[1]) / 2.0;
+            let p12 = (p[1] + p[2]) / 2.0;
+            let p23 = (p[2] + p[3]) / 2.0;
+            let p012 = (p01 + p12) / 2.0;
+            let p123 = (p12 + p23) / 2.0;
+            let mid = (p012 + p123) / 2.0;
+
+            flatten(points, [p[0], p01, p012, mid], tolerance, depth - 1);
+            flatten(points, [mid, p123, p23, p[3]], tolerance, depth - 1);
+        }
+
+        if self.points.is_empty() {
+            self.points.push(Vec2::ZERO);
+        }
+
+        let p0 = self.points[self.points.len() - 1];
+
+        flatten(&mut self.points, [p0, c1, c2, end], tolerance, 16);
+
+        let tangent = [end - c2, end - c1, end - p0]
+            .iter()
+            .find(|v| v.length_squared() > 0.0)
+            .copied();
+
+        self.direction = tangent.map(Vec2::normalize);
+    }
+
+    /// Transforms every point and the direction by `affine`.
+    ///
+    /// The direction is unset if `affine` collapses it.
+    #[inline]
+    pub fn transform(&mut self, affine: Affine2) {
+        for point in &mut self.points {
+            *point = affine.transform_point2(*point);
+        }
+
+        self.direction = self
+            .direction
+            .map(|direction| affine.transform_vector2(direction).normalize_or_zero())
+            .filter(|direction| *direction != Vec2::ZERO);
+    }
+
+    /// Total length of the line.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.points.windows(2).map(|w| w[0].distance(w[1])).sum()
+    }
+
+    /// Center of mass of the line, or `None` if self is empty.
+    ///
+    /// Falls back to the average of the points if the line has no length.
+    #[inline]
+    pub fn centroid(&self) -> Option<Vec2> {
+        if self.points.is_empty() {
+            return None;
+        }
+
+        let length = self.length();
+
+        if length == 0.0 {
+            return Some(polygon::mean(&self.points));
+        }
+
+        let centroid = self
+            .points
+            .windows(2)
+            .map(|w| (w[0] + w[1]) / 2.0 * w[0].distance(w[1]))
+            .fold(Vec2::ZERO, |sum, p| sum + p);
+
+        Some(centroid / length)
+    }
+
+    /// Axis aligned bounding box as `(min, max)`, min is greater than max if self is empty.
+    #[inline]
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        polygon::bounds(&self.points)
+    }
+
+    /// Measures distances along the line, see [`PathMeasure`].
+    #[inline]
+    pub fn measure(&self) -> PathMeasure {
+        PathMeasure::from(self)
+    }
+
+    /// Closest point on the line to `point`, or `None` if self is empty.
+    #[inline]
+    pub fn closest_point(&self, point: Vec2) -> Option<Vec2> {
+        if self.points.len() == 1 {
+            return Some(self.points[0]);
+        }
+
+        polygon::closest_point(self.points.windows(2).map(|w| (w[0], w[1])), point)
+    }
+
+    /// Distance from `point` to the line, infinite if self is empty.
+    #[inline]
+    pub fn distance(&self, point: Vec2) -> f32 {
+        self.closest_point(point)
+            .map_or(f32::INFINITY, |closest| closest.distance(point))
+    }
+
+    /// Direction at the end of the line.
+    ///
+    /// # Panics
+    /// If the direction isn't set and there are less than two points, see
+    /// [`Polyline::try_direction`].
     #[inline]
     pub fn direction(&self) -> Vec2 {
+        self.try_direction().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Direction at the end of the line, either the set direction or that of the last segment.
+    #[inline]
+    pub fn try_direction(&self) -> Result<Vec2, Error> {
         if let Some(direction) = self.direction {
-            direction
-        } else {
-            let p1 = self.points[self.points.len() - 1];
-            let p0 = self.points[self.points.len() - 2];
+            return Ok(direction);
+        }
 
-            (p1 - p0).normalize()
+        if self.points.len() < 2 {
+            return Err(Error::Degenerate(
+                "polyline must have a direction or at least two points",
+            ));
+        }
+
+        let p1 = self.points[self.points.len() - 1];
+        let p0 = self.points[self.points.len() - 2];
+
+        let direction = (p1 - p0).normalize_or_zero();
+
+        if direction == Vec2::ZERO {
+            if p1.is_finite() && p0.is_finite() {
+                Err(Error::Degenerate("last segment of polyline has zero length"))
+            } else {
+                Err(Error::NonFinite)
+            }
+        } else {
+            Ok(direction)
         }
     }
 }
@@ -34,3 +201,49 @@ impl From<Vec<Vec2>> for Polyline {
         Self { points, direction: None }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries() {
+        let line = Polyline::from(vec![Vec2::ZERO, Vec2::new(2.0, 0.0), Vec2::new(2.0, 1.0)]);
+
+        assert_eq!(line.length(), 3.0);
+        assert_eq!(line.centroid(), Some(Vec2::new(4.0 / 3.0, 1.0 / 6.0)));
+        assert_eq!(line.bounds(), (Vec2::ZERO, Vec2::new(2.0, 1.0)));
+        assert_eq!(
+            line.closest_point(Vec2::new(3.0, 0.5)),
+            Some(Vec2::new(2.0, 0.5))
+        );
+        assert_eq!(line.distance(Vec2::new(1.0, 1.0)), 1.0);
+
+        let point = Polyline::from(vec![Vec2::ONE, Vec2::ONE]);
+        assert_eq!(point.centroid(), Some(Vec2::ONE));
+
+        let empty = Polyline::default();
+        assert_eq!(empty.centroid(), None);
+        assert_eq!(empty.distance(Vec2::ZERO), f32::INFINITY);
+    }
+
+    #[test]
+    fn curves() {
+        let mut line = Polyline::default();
+        line.quad_to(Vec2::new(1.0, 1.0), Vec2::new(2.0, 0.0), 0.01);
+
+        // starts at the origin, passes through the middle of the curve
+        assert_eq!(line.points[0], Vec2::ZERO);
+        assert_eq!(line.points[line.points.len() - 1], Vec2::new(2.0, 0.0));
+        assert!(line.distance(Vec2::new(1.0, 0.5)) <= 0.01);
+        let direction = line.direction.unwrap();
+        assert!(direction.distance(Vec2::new(1.0, -1.0).normalize()) < 1e-6);
+
+        // collinear control points overshooting both ends of the chord
+        let mut line = Polyline::from(vec![Vec2::ZERO]);
+        line.cubic_to(Vec2::new(4.0, 0.0), Vec2::new(-3.0, 0.0), Vec2::X, 0.01);
+
+        let (min, max) = line.bounds();
+        assert!(min.x < -0.28 && max.x > 1.28, "{} {}", min, max);
+    }
+}
@@ -1,13 +1,90 @@
+use std::f32::consts::PI;
+
+use crate::error::Error;
+
+/// Smallest angle in radians spanned by a segment of a flattened arc, such that a zero
+/// [`Tolerance`] doesn't require infinitely many segments.
+const MIN_ARC_ANGLE: f32 = 1e-4;
+
+/// Largest number of segments an arc is flattened into.
+const MAX_ARC_STEPS: usize = 1 << 20;
+
+/// Tolerance for flattening curves into line segments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    /// Maximum distance between the curve and the segments approximating it.
+    pub deviation: f32,
+    /// Maximum angle in radians between consecutive segments.
+    pub angle: f32,
+}
+
 /// Configuration for generation.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Config {
     /// Approximate maximum distance between points.
     pub resolution: f32,
+    /// Flattens curves adaptively by [`Tolerance`] instead of spacing points by `resolution`.
+    pub tolerance: Option<Tolerance>,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Self { resolution: 1.0 }
+        Self {
+            resolution: 1.0,
+            tolerance: None,
+        }
+    }
+}
+
+impl Config {
+    #[inline]
+    pub fn new(resolution: f32) -> Self {
+        Self {
+            resolution,
+            tolerance: None,
+        }
+    }
+
+    /// Sets the [`Tolerance`], with `angle` in radians.
+    #[inline]
+    pub fn tolerance(mut self, deviation: f32, angle: f32) -> Self {
+        self.tolerance = Some(Tolerance { deviation, angle });
+        self
+    }
+
+    /// Maximum deviation of flattened curves.
+    ///
+    /// Without a [`Tolerance`] this is a hundredth of `resolution`, as curves are flattened
+    /// more densely where they bend rather than spaced evenly.
+    #[inline]
+    pub fn flatness(&self) -> f32 {
+        match self.tolerance {
+            Some(tolerance) => tolerance.deviation,
+            None => self.resolution / 100.0,
+        }
+    }
+
+    /// Number of segments to flatten an arc with `radius` spanning `angle` radians into.
+    ///
+    /// Segments never span less than a ten thousandth of a radian with a [`Tolerance`], and
+    /// there are never more than 2<sup>20</sup> of them.
+    #[inline]
+    pub fn arc_steps(&self, radius: f32, angle: f32) -> usize {
+        let steps = match self.tolerance {
+            Some(tolerance) => {
+                // a chord spanning `a` deviates `radius * (1 - cos(a / 2))` from the arc
+                let a = if tolerance.deviation < radius {
+                    2.0 * (1.0 - tolerance.deviation / radius).acos()
+                } else {
+                    PI
+                };
+
+                angle.abs() / a.min(tolerance.angle).max(MIN_ARC_ANGLE)
+            }
+            None => angle.abs() * radius.abs() / self.resolution,
+        };
+
+        (steps.ceil() as usize).min(MAX_ARC_STEPS)
     }
 }
 
@@ -16,5 +93,48 @@ pub trait Shape {
     type Input;
     type Output;
 
+    /// Generates output, panicking on invalid input.
     fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output;
+
+    /// Generates output, returning an [`Error`] on invalid input instead of panicking.
+    ///
+    /// Built in shapes propagate errors from their inputs, shapes that can't fail don't need to
+    /// implement this.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        Ok(self.generate(cfg, input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arc_steps() {
+        let cfg = Config::new(0.1);
+        assert_eq!(cfg.arc_steps(2.0, PI), 63);
+
+        // deviating at most the tolerance from the arc
+        let cfg = Config::default().tolerance(0.01, PI);
+        let steps = cfg.arc_steps(1.0, 2.0 * PI);
+        let deviation = 1.0 - (PI / steps as f32).cos();
+        assert!(deviation <= 0.01 && steps < 40, "{} {}", steps, deviation);
+
+        // limited by the angle
+        let cfg = Config::default().tolerance(1.0, 0.1);
+        assert_eq!(cfg.arc_steps(1.0, 2.0 * PI), 63);
+    }
+
+    #[test]
+    fn arc_steps_bounds() {
+        // a zero tolerance is limited by the smallest angle of a segment
+        let cfg = Config::default().tolerance(0.0, 0.0);
+        assert_eq!(cfg.arc_steps(1.0, PI), (PI / MIN_ARC_ANGLE).ceil() as usize);
+
+        // and the step count in general
+        assert_eq!(cfg.arc_steps(1.0, 1e6), MAX_ARC_STEPS);
+        assert_eq!(Config::new(0.0).arc_steps(1.0, PI), MAX_ARC_STEPS);
+        assert_eq!(Config::new(1e-30).arc_steps(1.0, PI), MAX_ARC_STEPS);
+    }
 }
@@ -0,0 +1,141 @@
+use std::f32::consts::{PI, TAU};
+
+use glam::Vec2;
+
+use crate::{error::Error, holed_polygon::HoledPolygon, polygon::Polygon, Config, Shape};
+
+/// Creates a ring between `inner_radius` and `outer_radius`, or a sector of it from angle
+/// `start` to `end` in radians, counter clockwise from the positive x axis.
+///
+/// A full ring has the inner circle as a hole, unless `inner_radius` is zero, a sector has no
+/// holes. All resulting polygons
+/// will be:
+/// * Simple
+/// * CCW
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annulus {
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Annulus {
+    /// Creates a full ring.
+    #[inline]
+    pub fn new(inner_radius: f32, outer_radius: f32) -> Self {
+        Self {
+            inner_radius,
+            outer_radius,
+            start: 0.0,
+            end: TAU,
+        }
+    }
+
+    /// Only keeps the sector from angle `start` to `end`.
+    #[inline]
+    pub fn sector(mut self, start: f32, end: f32) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+}
+
+/// Creates a convex ccw circle.
+#[inline]
+fn circle(cfg: &Config, radius: f32) -> Polygon {
+    let steps = cfg.arc_steps(radius, TAU).max(3);
+
+    let mut polygon = Polygon::with_capacity(steps);
+
+    for step in 0..steps {
+        let a = step as f32 / steps as f32 * TAU;
+
+        polygon.push(Vec2::new(a.cos(), a.sin()) * radius);
+    }
+
+    polygon.is_ccw = Some(true);
+    polygon.is_convex = Some(true);
+    polygon.is_simple = true;
+
+    polygon
+}
+
+impl Shape for Annulus {
+    type Input = ();
+    type Output = HoledPolygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, _: Self::Input) -> Self::Output {
+        let (start, end) = if self.end < self.start {
+            (self.end, self.start)
+        } else {
+            (self.start, self.end)
+        };
+
+        if end - start >= TAU {
+            let holes = if self.inner_radius > 0.0 {
+                vec![circle(cfg, self.inner_radius)]
+            } else {
+                Vec::new()
+            };
+
+            return HoledPolygon {
+                polygon: circle(cfg, self.outer_radius),
+                holes,
+            };
+        }
+
+        let angle = end - start;
+
+        let outer_steps = cfg.arc_steps(self.outer_radius, angle).max(1);
+        let inner_steps = cfg.arc_steps(self.inner_radius, angle).max(1);
+
+        let mut polygon = Polygon::with_capacity(outer_steps + inner_steps + 2);
+
+        // along the outside counter clockwise, then back along the inside
+        for step in 0..=outer_steps {
+            let a = start + angle * step as f32 / outer_steps as f32;
+
+            polygon.push(Vec2::new(a.cos(), a.sin()) * self.outer_radius);
+        }
+
+        if self.inner_radius > 0.0 {
+            for step in (0..=inner_steps).rev() {
+                let a = start + angle * step as f32 / inner_steps as f32;
+
+                polygon.push(Vec2::new(a.cos(), a.sin()) * self.inner_radius);
+            }
+        } else {
+            // a pie, the inner arc collapses into the center
+            polygon.push(Vec2::ZERO);
+        }
+
+        polygon.is_ccw = Some(true);
+        polygon.is_convex = Some(self.inner_radius <= 0.0 && angle <= PI);
+        polygon.is_simple = true;
+
+        polygon.into()
+    }
+
+    /// Returns an error if the outer radius isn't positive, the inner radius isn't between zero
+    /// and the outer radius, or the sector spans no angle.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::positive(self.outer_radius, "outer radius must be positive")?;
+
+        if !self.inner_radius.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        if self.inner_radius < 0.0 || self.inner_radius >= self.outer_radius {
+            return Err(Error::Degenerate(
+                "inner radius must be between zero and the outer radius",
+            ));
+        }
+
+        crate::error::positive((self.end - self.start).abs(), "annulus must span an angle")?;
+
+        Ok(self.generate(cfg, input))
+    }
+}
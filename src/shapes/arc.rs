@@ -0,0 +1,129 @@
+use std::f32::consts::{PI, TAU};
+
+use glam::Vec2;
+
+use crate::{error::Error, polygon::Polygon, polyline::Polyline, Config, Shape};
+
+/// Pushes points on an arc of `radius` from angle `start` to `end`, including both ends.
+#[inline]
+fn push_arc(points: &mut Vec<Vec2>, cfg: &Config, radius: f32, start: f32, end: f32) {
+    let steps = cfg.arc_steps(radius, end - start).max(1);
+
+    for step in 0..=steps {
+        let a = start + (end - start) * step as f32 / steps as f32;
+
+        points.push(Vec2::new(a.cos(), a.sin()) * radius);
+    }
+}
+
+/// Creates a circular arc from angle `start` to `end` in radians, counter clockwise from the
+/// positive x axis.
+///
+/// The direction of the resulting polyline is the tangent at the end.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Arc {
+    pub radius: f32,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Arc {
+    #[inline]
+    pub fn new(radius: f32, start: f32, end: f32) -> Self {
+        Self { radius, start, end }
+    }
+}
+
+impl Shape for Arc {
+    type Input = ();
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, _: Self::Input) -> Self::Output {
+        let mut polyline = Polyline::default();
+
+        push_arc(&mut polyline.points, cfg, self.radius, self.start, self.end);
+
+        let tangent = Vec2::new(-self.end.sin(), self.end.cos());
+        polyline.direction = Some(if self.end < self.start {
+            -tangent
+        } else {
+            tangent
+        });
+
+        polyline
+    }
+
+    /// Returns an error if the radius isn't positive or the angles aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::positive(self.radius, "radius must be positive")?;
+
+        if !self.start.is_finite() || !self.end.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        Ok(self.generate(cfg, input))
+    }
+}
+
+/// Creates a circular sector from angle `start` to `end` in radians, counter clockwise from the
+/// positive x axis.
+///
+/// The resulting polygon will always be:
+/// * Simple
+/// * CCW
+/// * Convex if the sector spans at most half a circle, or the whole circle
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pie {
+    pub radius: f32,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Pie {
+    #[inline]
+    pub fn new(radius: f32, start: f32, end: f32) -> Self {
+        Self { radius, start, end }
+    }
+}
+
+impl Shape for Pie {
+    type Input = ();
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, _: Self::Input) -> Self::Output {
+        let (start, end) = if self.end < self.start {
+            (self.end, self.start)
+        } else {
+            (self.start, self.end)
+        };
+
+        let mut polygon = Polygon::default();
+
+        if end - start >= TAU {
+            // a full circle has no center point
+            push_arc(&mut polygon.points, cfg, self.radius, start, start + TAU);
+            polygon.points.pop();
+        } else {
+            polygon.push(Vec2::ZERO);
+            push_arc(&mut polygon.points, cfg, self.radius, start, end);
+        }
+
+        polygon.is_ccw = Some(true);
+        polygon.is_convex = Some(end - start <= PI || end - start >= TAU);
+        polygon.is_simple = true;
+
+        polygon
+    }
+
+    /// Returns an error if the radius isn't positive or the sector spans no angle.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::positive(self.radius, "radius must be positive")?;
+        crate::error::positive((self.end - self.start).abs(), "pie must span an angle")?;
+
+        Ok(self.generate(cfg, input))
+    }
+}
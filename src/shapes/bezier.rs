@@ -0,0 +1,58 @@
+use glam::Vec2;
+
+use crate::{error::Error, polyline::Polyline, Config, Shape};
+
+/// Adds a quadratic bézier curve to the end of a [`Polyline`], see [`Polyline::quad_to`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuadTo {
+    pub ctrl: Vec2,
+    pub end: Vec2,
+}
+
+impl Shape for QuadTo {
+    type Input = Polyline;
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, mut input: Self::Input) -> Self::Output {
+        input.quad_to(self.ctrl, self.end, cfg.flatness());
+
+        input
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&input.points)?;
+        crate::error::finite(&[self.ctrl, self.end])?;
+
+        Ok(self.generate(cfg, input))
+    }
+}
+
+/// Adds a cubic bézier curve to the end of a [`Polyline`], see [`Polyline::cubic_to`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CubicTo {
+    pub c1: Vec2,
+    pub c2: Vec2,
+    pub end: Vec2,
+}
+
+impl Shape for CubicTo {
+    type Input = Polyline;
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, mut input: Self::Input) -> Self::Output {
+        input.cubic_to(self.c1, self.c2, self.end, cfg.flatness());
+
+        input
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&input.points)?;
+        crate::error::finite(&[self.c1, self.c2, self.end])?;
+
+        Ok(self.generate(cfg, input))
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    boolean::boolean, error::Error, holed_polygon::HoledPolygon, polygon::Polygon, Config, Shape,
+};
+
+/// Operation performed by [`Boolean`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BooleanOp {
+    /// Area covered by either shape.
+    Union,
+    /// Area covered by both shapes.
+    Intersection,
+    /// Area covered by the input but not the other shape.
+    Difference,
+    /// Area covered by exactly one of the shapes.
+    Xor,
+}
+
+/// Combines the input with another shape using a [`BooleanOp`].
+///
+/// The resulting polygons will always be:
+/// * Simple
+/// * CCW, holes included
+#[derive(Clone, Debug)]
+pub struct Boolean<T, S> {
+    pub other: S,
+    pub op: BooleanOp,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T, S> Boolean<T, S> {
+    #[inline]
+    pub const fn new(other: S, op: BooleanOp) -> Self {
+        Self {
+            other,
+            op,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+/// Checks that every point of `polygons` is finite.
+#[inline]
+fn finite(polygons: &[HoledPolygon]) -> Result<(), Error> {
+    for polygon in polygons {
+        crate::error::finite(&polygon.polygon.points)?;
+
+        for hole in &polygon.holes {
+            crate::error::finite(&hole.points)?;
+        }
+    }
+
+    Ok(())
+}
+
+impl<S> Shape for Boolean<Polygon, S>
+where
+    S: Shape<Input = ()>,
+    S::Output: Into<HoledPolygon>,
+{
+    type Input = Polygon;
+    type Output = Vec<HoledPolygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, polygon: Self::Input) -> Self::Output {
+        let other = self.other.generate(cfg, ()).into();
+
+        boolean(&[polygon.into()], &[other], self.op)
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polygon: Self::Input) -> Result<Self::Output, Error> {
+        let polygons = [polygon.into()];
+        let other = [self.other.try_generate(cfg, ())?.into()];
+
+        finite(&polygons)?;
+        finite(&other)?;
+
+        Ok(boolean(&polygons, &other, self.op))
+    }
+}
+
+impl<S> Shape for Boolean<HoledPolygon, S>
+where
+    S: Shape<Input = ()>,
+    S::Output: Into<HoledPolygon>,
+{
+    type Input = HoledPolygon;
+    type Output = Vec<HoledPolygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, polygon: Self::Input) -> Self::Output {
+        let other = self.other.generate(cfg, ()).into();
+
+        boolean(&[polygon], &[other], self.op)
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polygon: Self::Input) -> Result<Self::Output, Error> {
+        let polygons = [polygon];
+        let other = [self.other.try_generate(cfg, ())?.into()];
+
+        finite(&polygons)?;
+        finite(&other)?;
+
+        Ok(boolean(&polygons, &other, self.op))
+    }
+}
+
+impl<S> Shape for Boolean<Vec<HoledPolygon>, S>
+where
+    S: Shape<Input = ()>,
+    S::Output: Into<HoledPolygon>,
+{
+    type Input = Vec<HoledPolygon>;
+    type Output = Vec<HoledPolygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, polygons: Self::Input) -> Self::Output {
+        let other = self.other.generate(cfg, ()).into();
+
+        boolean(&polygons, &[other], self.op)
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polygons: Self::Input) -> Result<Self::Output, Error> {
+        let other = [self.other.try_generate(cfg, ())?.into()];
+
+        finite(&polygons)?;
+        finite(&other)?;
+
+        Ok(boolean(&polygons, &other, self.op))
+    }
+}
@@ -0,0 +1,74 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use glam::Vec2;
+
+use crate::{error::Error, polygon::Polygon, Config, Shape};
+
+/// Creates a horizontal capsule, a rectangle `length` long with half circles of `radius` on
+/// both ends.
+///
+/// A negative `length` is treated as zero, resulting in a circle. The resulting polygon will
+/// always be:
+/// * Simple
+/// * Convex
+/// * CCW
+#[derive(Clone, Debug, PartialEq)]
+pub struct Capsule {
+    pub length: f32,
+    pub radius: f32,
+}
+
+impl Capsule {
+    #[inline]
+    pub fn new(length: f32, radius: f32) -> Self {
+        Self { length, radius }
+    }
+}
+
+impl Shape for Capsule {
+    type Input = ();
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, _: Self::Input) -> Self::Output {
+        let steps = cfg.arc_steps(self.radius, PI).max(1);
+        let length = self.length.max(0.0);
+
+        let mut polygon = Polygon::with_capacity(steps * 2 + 2);
+
+        for (center, start) in [
+            (Vec2::new(length / 2.0, 0.0), -FRAC_PI_2),
+            (Vec2::new(-length / 2.0, 0.0), FRAC_PI_2),
+        ] {
+            for step in 0..=steps {
+                let a = start + PI * step as f32 / steps as f32;
+
+                polygon.push(center + Vec2::new(a.cos(), a.sin()) * self.radius);
+            }
+        }
+
+        if length == 0.0 {
+            // the half circles meet, sharing their end points
+            polygon.points.dedup();
+            polygon.points.pop();
+        }
+
+        polygon.is_ccw = Some(true);
+        polygon.is_convex = Some(true);
+        polygon.is_simple = true;
+
+        polygon
+    }
+
+    /// Returns an error if the radius isn't positive or the length isn't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::positive(self.radius, "radius must be positive")?;
+
+        if !self.length.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        Ok(self.generate(cfg, input))
+    }
+}
@@ -0,0 +1,140 @@
+use glam::Vec2;
+
+use crate::{error::Error, polygon::Polygon, polyline::Polyline, Config, Shape};
+
+/// Interpolating spline passing through every point.
+///
+/// Create with [`CatmullRom::open`] for a [`Polyline`] or [`CatmullRom::closed`] for a
+/// [`Polygon`]. Segments are flattened like bézier curves, see [`Config::flatness`].
+#[derive(Clone, Debug)]
+pub struct CatmullRom<T> {
+    pub points: Vec<Vec2>,
+    /// Parametrization of the knots, 0 for uniform, 0.5 for centripetal and 1 for chordal.
+    ///
+    /// Centripetal, the default, never forms cusps or loops within a segment.
+    pub alpha: f32,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl CatmullRom<Polyline> {
+    /// Spline from the first to the last point.
+    #[inline]
+    pub fn open(points: impl IntoIterator<Item = impl Into<Vec2>>) -> Self {
+        Self::new(points)
+    }
+}
+
+impl CatmullRom<Polygon> {
+    /// Spline connecting the last point back to the first.
+    #[inline]
+    pub fn closed(points: impl IntoIterator<Item = impl Into<Vec2>>) -> Self {
+        Self::new(points)
+    }
+}
+
+impl<T> CatmullRom<T> {
+    #[inline]
+    fn new(points: impl IntoIterator<Item = impl Into<Vec2>>) -> Self {
+        Self {
+            points: points.into_iter().map(Into::into).collect(),
+            alpha: 0.5,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets the parametrization, see [`CatmullRom::alpha`].
+    #[inline]
+    pub fn alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Flattens the spline through `points`, which are connected end to end if `closed`.
+    fn spline(&self, cfg: &Config, closed: bool) -> Result<Polyline, Error> {
+        crate::error::finite(&self.points)?;
+
+        let mut points = self.points.clone();
+        points.dedup();
+
+        if closed && points.len() > 1 && points[0] == points[points.len() - 1] {
+            points.pop();
+        }
+
+        if points.len() < 2 {
+            return Err(Error::Degenerate("spline requires at least two distinct points"));
+        }
+
+        let len = points.len();
+
+        // open ends are extended by mirroring the neighbouring point
+        let at = |i: isize| -> Vec2 {
+            if closed {
+                points[i.rem_euclid(len as isize) as usize]
+            } else if i < 0 {
+                points[0] * 2.0 - points[1]
+            } else if i as usize >= len {
+                points[len - 1] * 2.0 - points[len - 2]
+            } else {
+                points[i as usize]
+            }
+        };
+
+        let mut line = Polyline::from(vec![points[0]]);
+        let segments = if closed { len } else { len - 1 };
+
+        for i in 0..segments as isize {
+            let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+
+            let t01 = p0.distance(p1).powf(self.alpha).max(f32::EPSILON);
+            let t12 = p1.distance(p2).powf(self.alpha).max(f32::EPSILON);
+            let t23 = p2.distance(p3).powf(self.alpha).max(f32::EPSILON);
+
+            // tangents of the non uniform spline, scaled to the segment
+            let m1 = ((p1 - p0) / t01 - (p2 - p0) / (t01 + t12) + (p2 - p1) / t12) * t12;
+            let m2 = ((p2 - p1) / t12 - (p3 - p1) / (t12 + t23) + (p3 - p2) / t23) * t12;
+
+            line.cubic_to(p1 + m1 / 3.0, p2 - m2 / 3.0, p2, cfg.flatness());
+        }
+
+        Ok(line)
+    }
+}
+
+impl Shape for CatmullRom<Polyline> {
+    type Input = ();
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, _: Self::Input) -> Result<Self::Output, Error> {
+        self.spline(cfg, false)
+    }
+}
+
+impl Shape for CatmullRom<Polygon> {
+    type Input = ();
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, _: Self::Input) -> Result<Self::Output, Error> {
+        let mut line = self.spline(cfg, true)?;
+
+        // the last segment ends at the first point
+        line.points.pop();
+
+        if line.points.len() < 3 {
+            return Err(Error::Degenerate("closed spline requires at least three points"));
+        }
+
+        Ok(Polygon::from(line.points))
+    }
+}
@@ -0,0 +1,100 @@
+use glam::Vec2;
+
+use crate::{
+    error::Error, holed_polygon::HoledPolygon, polygon::Polygon, polyline::Polyline, Config, Shape,
+};
+
+use super::round_corners::{corners, replace_corners};
+
+/// Cuts every corner of `points` off with a straight line, `distance` along both edges.
+fn chamfer(points: &[Vec2], closed: bool, distance: f32) -> Vec<Vec2> {
+    replace_corners(
+        points,
+        closed,
+        |_| distance,
+        |result, p, d0, d1, t| {
+            result.push(p - d0 * t);
+            result.push(p + d1 * t);
+        },
+    )
+}
+
+/// Cuts the corners of a shape off with straight lines, the distance is reduced where edges are
+/// too short to fit it.
+#[derive(Clone, Debug)]
+pub struct Chamfer<T> {
+    /// Distance from each corner along both edges to cut at.
+    pub distance: f32,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> Chamfer<T> {
+    #[inline]
+    pub const fn new(distance: f32) -> Self {
+        Self {
+            distance,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl Shape for Chamfer<Polyline> {
+    type Input = Polyline;
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&input.points)?;
+
+        Ok(Polyline {
+            points: chamfer(&input.points, false, self.distance),
+            direction: input.direction,
+        })
+    }
+}
+
+impl Shape for Chamfer<Polygon> {
+    type Input = Polygon;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&input.points)?;
+
+        Ok(corners(input, |points| chamfer(points, true, self.distance)))
+    }
+}
+
+impl Shape for Chamfer<HoledPolygon> {
+    type Input = HoledPolygon;
+    type Output = HoledPolygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        let ring = |polygon: Polygon| {
+            crate::error::finite(&polygon.points)?;
+
+            Ok(corners(polygon, |points| chamfer(points, true, self.distance)))
+        };
+
+        Ok(HoledPolygon {
+            polygon: ring(input.polygon)?,
+            holes: input.holes.into_iter().map(ring).collect::<Result<_, _>>()?,
+        })
+    }
+}
@@ -2,7 +2,7 @@ use std::f32::consts::TAU;
 
 use glam::Vec2;
 
-use crate::{polygon::Polygon, Shape};
+use crate::{error::Error, polygon::Polygon, Shape};
 
 /// Creates a circle.
 ///
@@ -28,9 +28,8 @@ impl Shape for Circle {
 
     #[inline]
     fn generate(&self, cfg: &crate::Config, _: Self::Input) -> Self::Output {
-        let dist = self.radius * TAU;
         // calculate number of steps
-        let steps = (dist / cfg.resolution).ceil() as usize;
+        let steps = cfg.arc_steps(self.radius, TAU);
 
         let mut polygon = Polygon::default();
 
@@ -47,4 +46,12 @@ impl Shape for Circle {
 
         polygon
     }
+
+    /// Returns an error if the radius isn't positive.
+    #[inline]
+    fn try_generate(&self, cfg: &crate::Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::positive(self.radius, "radius must be positive")?;
+
+        Ok(self.generate(cfg, input))
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{mesh::Mesh, Config, Shape};
+use crate::{error::Error, mesh::Mesh, Config, Shape};
 
 #[derive(Clone, Debug)]
 pub struct Combine<I, O> {
@@ -15,9 +15,15 @@ where
     type Output = O::Output;
 
     #[inline]
-    fn generate(&self, cfg: &crate::Config, input: Self::Input) -> Self::Output {
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
         self.output.generate(cfg, self.input.generate(cfg, input))
     }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.output
+            .try_generate(cfg, self.input.try_generate(cfg, input)?)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -33,18 +39,17 @@ where
     type Output = Mesh;
 
     #[inline]
-    fn generate(&self, cfg: &Config, mut mesh: Self::Input) -> Self::Output {
-        let mut add_mesh = self.mesh.generate(cfg, ());
+    fn generate(&self, cfg: &Config, mesh: Self::Input) -> Self::Output {
+        let add_mesh = self.mesh.generate(cfg, ());
 
-        let index = mesh.vertices.len() as u32;
+        CombineMeshes.generate(cfg, (mesh, add_mesh))
+    }
 
-        mesh.vertices.append(&mut add_mesh.vertices);
-        add_mesh
-            .indices
-            .into_iter()
-            .for_each(|i| mesh.indices.push(index + i));
+    #[inline]
+    fn try_generate(&self, cfg: &Config, mesh: Self::Input) -> Result<Self::Output, Error> {
+        let add_mesh = self.mesh.try_generate(cfg, ())?;
 
-        mesh
+        Ok(CombineMeshes.generate(cfg, (mesh, add_mesh)))
     }
 }
 
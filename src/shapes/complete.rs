@@ -1,4 +1,4 @@
-use crate::{polygon::Polygon, polyline::Polyline, Config, Shape};
+use crate::{error::Error, polygon::Polygon, polyline::Polyline, Config, Shape};
 
 /// Converts a [`Polyline`] to a [`Polygon`], by connecting the start and end points.
 #[derive(Clone, Debug)]
@@ -12,4 +12,16 @@ impl Shape for Complete {
     fn generate(&self, _cfg: &Config, polyline: Self::Input) -> Self::Output {
         Polygon::from(polyline.points)
     }
+
+    /// Returns an error if the line has fewer than three points or they aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polyline: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&polyline.points)?;
+
+        if polyline.points.len() < 3 {
+            return Err(Error::Degenerate("polygon must have at least three points"));
+        }
+
+        Ok(self.generate(cfg, polyline))
+    }
 }
@@ -0,0 +1,125 @@
+use crate::{
+    decompose, error::Error, holed_polygon::HoledPolygon, polygon::Polygon, Config, Shape,
+};
+
+/// Algorithm used by [`ConvexDecompose`] to split polygons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decomposition {
+    /// Triangulates and then removes diagonals as long as the pieces stay convex, guaranteed to
+    /// produce at most four times the optimal number of pieces.
+    HertelMehlhorn,
+    /// Splits at reflex vertices, adding points where necessary, usually producing fewer
+    /// pieces than [`Decomposition::HertelMehlhorn`] at a greater cost.
+    Bayazit,
+}
+
+impl Default for Decomposition {
+    #[inline]
+    fn default() -> Self {
+        Self::HertelMehlhorn
+    }
+}
+
+/// Splits polygons into convex pieces, for example for physics colliders.
+///
+/// Holes are bridged to the outside before decomposing.
+#[derive(Clone, Debug)]
+pub struct ConvexDecompose<T> {
+    pub decomposition: Decomposition,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> ConvexDecompose<T> {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            decomposition: Decomposition::HertelMehlhorn,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets the [`Decomposition`] algorithm.
+    #[inline]
+    pub fn decomposition(mut self, decomposition: Decomposition) -> Self {
+        self.decomposition = decomposition;
+        self
+    }
+
+    #[inline]
+    fn decompose(&self, mut polygon: HoledPolygon) -> Result<Vec<Polygon>, Error> {
+        polygon.try_verify()?;
+
+        if polygon.holes.is_empty() && polygon.polygon.is_convex() {
+            polygon.polygon.is_convex = Some(true);
+
+            return Ok(vec![polygon.polygon]);
+        }
+
+        for hole in polygon.holes {
+            polygon.polygon.try_merge_hole(hole)?;
+        }
+
+        match self.decomposition {
+            Decomposition::HertelMehlhorn => decompose::hertel_mehlhorn(&polygon.polygon),
+            Decomposition::Bayazit => decompose::bayazit(&polygon.polygon),
+        }
+    }
+}
+
+impl<T> Default for ConvexDecompose<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shape for ConvexDecompose<Polygon> {
+    type Input = Polygon;
+    type Output = Vec<Polygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.decompose(HoledPolygon::from(input))
+    }
+}
+
+impl Shape for ConvexDecompose<HoledPolygon> {
+    type Input = HoledPolygon;
+    type Output = Vec<Polygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.decompose(input)
+    }
+}
+
+impl Shape for ConvexDecompose<Vec<HoledPolygon>> {
+    type Input = Vec<HoledPolygon>;
+    type Output = Vec<Polygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        let mut pieces = Vec::new();
+
+        for polygon in input {
+            pieces.append(&mut self.decompose(polygon)?);
+        }
+
+        Ok(pieces)
+    }
+}
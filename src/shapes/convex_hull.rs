@@ -0,0 +1,174 @@
+use glam::Vec2;
+
+use crate::{
+    error::Error, holed_polygon::HoledPolygon, mesh::Mesh, polygon::Polygon, polyline::Polyline,
+    Config, Shape,
+};
+
+/// Smallest convex polygon containing every point, using the monotone chain algorithm.
+fn hull(mut points: Vec<Vec2>) -> Result<Polygon, Error> {
+    crate::error::finite(&points)?;
+
+    points.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap()
+            .then(a.y.partial_cmp(&b.y).unwrap())
+    });
+    points.dedup();
+
+    let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() + 1);
+
+    // lower half left to right, then upper half right to left, both turning ccw
+    for pass in 0..2 {
+        let start = hull.len();
+
+        for i in 0..points.len() {
+            let p = if pass == 0 {
+                points[i]
+            } else {
+                points[points.len() - 1 - i]
+            };
+
+            while hull.len() >= start + 2 {
+                let a = hull[hull.len() - 2];
+                let b = hull[hull.len() - 1];
+
+                if (b - a).perp_dot(p - b) > 0.0 {
+                    break;
+                }
+
+                hull.pop();
+            }
+
+            hull.push(p);
+        }
+
+        // the last point is the first of the other half
+        hull.pop();
+    }
+
+    if hull.len() < 3 {
+        return Err(Error::Degenerate(
+            "convex hull requires at least three points not on a line",
+        ));
+    }
+
+    let mut polygon = Polygon::from(hull);
+    polygon.is_ccw = Some(true);
+    polygon.is_convex = Some(true);
+    polygon.is_simple = true;
+
+    Ok(polygon)
+}
+
+/// Creates the smallest convex [`Polygon`] containing all points of the geometry.
+///
+/// Useful for simple colliders and bounding shapes.
+#[derive(Clone, Debug)]
+pub struct ConvexHull<T> {
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> ConvexHull<T> {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ConvexHull<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shape for ConvexHull<Polyline> {
+    type Input = Polyline;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        hull(input.points)
+    }
+}
+
+impl Shape for ConvexHull<Polygon> {
+    type Input = Polygon;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        hull(input.points)
+    }
+}
+
+impl Shape for ConvexHull<HoledPolygon> {
+    type Input = HoledPolygon;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Holes lie within the polygon and are thus ignored.
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        hull(input.polygon.points)
+    }
+}
+
+impl Shape for ConvexHull<Vec<HoledPolygon>> {
+    type Input = Vec<HoledPolygon>;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        hull(
+            input
+                .into_iter()
+                .flat_map(|polygon| polygon.polygon.points)
+                .collect(),
+        )
+    }
+}
+
+impl Shape for ConvexHull<Mesh> {
+    type Input = Mesh;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Hull of the vertex positions projected onto the xy plane.
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        hull(
+            input
+                .vertices
+                .iter()
+                .map(|vertex| vertex.position.truncate())
+                .collect(),
+        )
+    }
+}
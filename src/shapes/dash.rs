@@ -0,0 +1,265 @@
+use crate::{
+    error::Error, path_measure::PathMeasure, polygon::Polygon, polyline::Polyline, Config, Shape,
+};
+
+use super::trim::extract;
+
+/// Maximum number of dashes, patterns much shorter than the path are rejected rather than
+/// running out of memory.
+const MAX_DASHES: usize = 1 << 20;
+
+/// Splits a path into dashes following `pattern`, alternating between the lengths of dashes
+/// and gaps like svg's `stroke-dasharray`, a pattern with an odd number of lengths is repeated
+/// to make it even.
+///
+/// The outline of a polygon is dashed from its first point. Dashes of length zero become single
+/// points, directed along the path, which [`Thicken`](super::Thicken) turns into dots.
+///
+/// An empty pattern keeps the path whole, while a pattern so short compared to the path that it
+/// results in an excessive number of dashes is degenerate.
+#[derive(Clone, Debug)]
+pub struct Dash<T> {
+    pub pattern: Vec<f32>,
+    /// Distance into the pattern the path starts at.
+    pub offset: f32,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> Dash<T> {
+    #[inline]
+    pub fn new(pattern: &[f32]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+            offset: 0.0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets the [`Dash::offset`].
+    #[inline]
+    pub fn offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Dashes the path through `measure`, a dot at the end of a `closed` path is left out as it
+    /// repeats its start.
+    fn dash(&self, measure: &PathMeasure, closed: bool) -> Result<Vec<Polyline>, Error> {
+        if !self.offset.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        let mut pattern = self.pattern.clone();
+
+        if pattern.iter().any(|length| !length.is_finite()) {
+            return Err(Error::NonFinite);
+        }
+
+        if pattern.iter().any(|length| *length < 0.0) {
+            return Err(Error::Degenerate("dash pattern must not be negative"));
+        }
+
+        let length = measure.length();
+
+        if pattern.is_empty() {
+            return Ok(vec![extract(measure, 0.0, length)]);
+        }
+
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_slice(&self.pattern);
+        }
+
+        let period: f32 = pattern.iter().sum();
+
+        if period <= 0.0 {
+            return Err(Error::Degenerate("dash pattern must have a length"));
+        }
+
+        // dashes and gaps along the path, including those the offset shifts before its start
+        let count = ((length / period).ceil() + 1.0) * pattern.len() as f32;
+
+        if count > MAX_DASHES as f32 {
+            return Err(Error::Degenerate("dash pattern is too short for the path"));
+        }
+
+        // find where in the pattern the path starts, `rem_euclid` may round up to the period
+        let mut i = 0;
+        let mut distance = -self.offset.rem_euclid(period);
+
+        while distance + pattern[i] <= 0.0 && pattern[i] > 0.0 {
+            distance += pattern[i];
+            i = (i + 1) % pattern.len();
+        }
+
+        let mut dashes = Vec::new();
+
+        // dashes are at even indices, gaps at odd
+        let is_dash = |i: usize| i & 1 == 0;
+        let dot_at_end = |distance: f32, i: usize| {
+            !closed && distance == length && pattern[i] == 0.0 && is_dash(i)
+        };
+
+        while distance < length || dot_at_end(distance, i) {
+            let end = distance + pattern[i];
+
+            // too small to advance the distance at this precision
+            if (end <= distance && pattern[i] > 0.0) || dashes.len() > count as usize {
+                return Err(Error::Degenerate("dash pattern is too short for the path"));
+            }
+
+            if is_dash(i) && end >= 0.0 {
+                dashes.push(extract(measure, distance.max(0.0), end.min(length)));
+            }
+
+            distance = end;
+            i = (i + 1) % pattern.len();
+        }
+
+        Ok(dashes)
+    }
+}
+
+impl Shape for Dash<Polyline> {
+    type Input = Polyline;
+    type Output = Vec<Polyline>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Returns no dashes if the line has no points.
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&input.points)?;
+
+        if input.points.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.dash(&input.measure(), false)
+    }
+}
+
+impl Shape for Dash<Polygon> {
+    type Input = Polygon;
+    type Output = Vec<Polyline>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Returns no dashes if the polygon has no points.
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&input.points)?;
+
+        if input.points.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.dash(&input.measure(), true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use glam::Vec2;
+
+    use crate::shapes::{LineCap, Thicken};
+
+    use super::*;
+
+    fn line(length: f32) -> Polyline {
+        Polyline {
+            points: vec![Vec2::ZERO, Vec2::new(length, 0.0)],
+            direction: None,
+        }
+    }
+
+    /// Start and end along the x axis of every dash of a line of `length`.
+    fn dash(pattern: &[f32], offset: f32, length: f32) -> Result<Vec<(f32, f32)>, Error> {
+        let dashes = Dash::<Polyline>::new(pattern)
+            .offset(offset)
+            .try_generate(&Config::default(), line(length))?;
+
+        Ok(dashes
+            .iter()
+            .map(|dash| (dash.points[0].x, dash.points[dash.points.len() - 1].x))
+            .collect())
+    }
+
+    #[test]
+    fn line_pattern() {
+        let expected = [(0.0, 2.0), (3.0, 5.0), (6.0, 8.0), (9.0, 10.0)];
+        assert_eq!(dash(&[2.0, 1.0], 0.0, 10.0).unwrap(), expected);
+
+        // odd patterns repeat, alternating dashes and gaps
+        assert_eq!(dash(&[1.0], 0.0, 4.0).unwrap(), [(0.0, 1.0), (2.0, 3.0)]);
+
+        // the offset shifts the pattern backwards
+        let expected = [(0.0, 1.0), (2.0, 4.0)];
+        assert_eq!(dash(&[2.0, 1.0], 1.0, 5.0).unwrap(), expected);
+
+        assert_eq!(dash(&[], 0.0, 5.0).unwrap(), [(0.0, 5.0)]);
+    }
+
+    #[test]
+    fn offset_rounding_to_period() {
+        // `rem_euclid` of a tiny negative offset rounds to the period
+        assert_eq!(dash(&[1.0, 1.0], -1e-9, 4.0).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn polygon_wraps() {
+        let square = Polygon::from(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ]);
+
+        // the last dash runs past the first point
+        let dashes = Dash::<Polygon>::new(&[1.5, 0.5])
+            .offset(1.0)
+            .generate(&Config::default(), square);
+
+        let length: f32 = dashes.iter().map(|dash| dash.measure().length()).sum();
+
+        assert!((length - 3.0).abs() < 1e-5);
+        assert_eq!(dashes.len(), 3);
+    }
+
+    #[test]
+    fn too_short() {
+        assert!(dash(&[1e-7, 1e-7], 0.0, 10.0).is_err());
+        assert!(dash(&[1.0, -1.0], 0.0, 10.0).is_err());
+        assert!(dash(&[0.0, 0.0], 0.0, 10.0).is_err());
+    }
+
+    #[test]
+    fn dots_become_circles() {
+        let cfg = Config::new(0.01);
+
+        let dots = Dash::<Polyline>::new(&[0.0, 1.0]).generate(&cfg, line(3.0));
+
+        // the dot at the end of an open line is kept
+        assert_eq!(dots.len(), 4);
+        assert!(dots.iter().all(|dot| dot.points.len() == 1));
+
+        let circles = Thicken::<Vec<Polyline>>::new(0.2)
+            .cap(LineCap::Round)
+            .generate(&cfg, dots);
+
+        assert_eq!(circles.len(), 4);
+
+        for circle in circles {
+            let area = circle.area();
+
+            assert!((area - PI * 0.01).abs() < 1e-3, "{}", area);
+        }
+    }
+}
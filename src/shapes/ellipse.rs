@@ -0,0 +1,58 @@
+use std::f32::consts::TAU;
+
+use glam::Vec2;
+
+use crate::{error::Error, polygon::Polygon, Config, Shape};
+
+/// Creates an ellipse with `radii` along the x and y axes.
+///
+/// The resulting polygon will always be:
+/// * Simple
+/// * Convex
+/// * CCW
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ellipse {
+    pub radii: Vec2,
+}
+
+impl Ellipse {
+    #[inline]
+    pub fn new(radii: impl Into<Vec2>) -> Self {
+        Self {
+            radii: radii.into(),
+        }
+    }
+}
+
+impl Shape for Ellipse {
+    type Input = ();
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, _: Self::Input) -> Self::Output {
+        let steps = cfg.arc_steps(self.radii.max_element(), TAU).max(3);
+
+        let mut polygon = Polygon::with_capacity(steps);
+
+        for step in 0..steps {
+            let a = (step as f32 / steps as f32) * TAU;
+
+            polygon.push(Vec2::new(a.cos(), a.sin()) * self.radii);
+        }
+
+        polygon.is_ccw = Some(true);
+        polygon.is_convex = Some(true);
+        polygon.is_simple = true;
+
+        polygon
+    }
+
+    /// Returns an error if a radius isn't positive.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::positive(self.radii.min_element(), "radii must be positive")?;
+        crate::error::positive(self.radii.max_element(), "radii must be positive")?;
+
+        Ok(self.generate(cfg, input))
+    }
+}
@@ -1,119 +1,654 @@
+use std::collections::{HashMap, HashSet};
+
+use glam::Vec2;
+
 use crate::{
+    boolean, delaunay,
+    error::Error,
     holed_polygon::HoledPolygon,
     mesh::{Mesh, Vertex},
+    paint::Paint,
     polygon::Polygon,
     Config, Shape,
 };
 
+use super::thicken::right;
+
+/// Algorithm used by [`Fill`] to triangulate polygons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Triangulation {
+    /// Ear clipping with holes bridged to the outside, fast but produces long sliver triangles.
+    EarClipping,
+    /// Constrained delaunay triangulation, maximizes the minimum angle of the triangles, which
+    /// is better suited for gradients, deformation and physics.
+    Delaunay,
+}
+
+impl Default for Triangulation {
+    #[inline]
+    fn default() -> Self {
+        Self::EarClipping
+    }
+}
+
+/// Rule deciding which regions of self intersecting polygons are filled, matching the svg
+/// `fill-rule` property.
+///
+/// The outer ring and each hole of a [`HoledPolygon`] are resolved separately, such that holes
+/// are cut out regardless of their winding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// Regions enclosed an odd number of times are filled.
+    EvenOdd,
+    /// Regions with a winding number other than zero are filled.
+    NonZero,
+}
+
+impl FillRule {
+    #[inline]
+    pub(super) fn is_inside(self, winding: i32) -> bool {
+        match self {
+            Self::EvenOdd => winding % 2 != 0,
+            Self::NonZero => winding != 0,
+        }
+    }
+}
+
+/// Triangulates polygons, coloring every vertex with a [`Paint`].
 #[derive(Clone, Debug)]
-pub struct Fill<T> {
-    pub color: [f32; 4],
+pub struct Fill<T, P> {
+    pub paint: P,
+    /// Maximum edge length of triangles, see [`Fill::refine`].
+    pub refine: Option<f32>,
+    pub triangulation: Triangulation,
+    /// Width of the antialiasing fringe, see [`Fill::antialias`].
+    pub antialias: Option<f32>,
+    /// How self intersections are resolved, see [`Fill::fill_rule`].
+    pub fill_rule: Option<FillRule>,
     _marker: std::marker::PhantomData<*const T>,
 }
 
-impl<T> Fill<T> {
+impl<T, P> Fill<T, P> {
     #[inline]
-    pub const fn new(color: [f32; 4]) -> Self {
+    pub const fn new(paint: P) -> Self {
         Self {
-            color,
+            paint,
+            refine: None,
+            triangulation: Triangulation::EarClipping,
+            antialias: None,
+            fill_rule: None,
             _marker: std::marker::PhantomData,
         }
     }
+
+    /// Splits polygons at their self intersections and fills the regions inside by `rule`.
+    ///
+    /// Without a rule, self intersections are removed by [`Polygon::verify`], which drops the
+    /// points between crossing edges.
+    #[inline]
+    pub fn fill_rule(mut self, rule: FillRule) -> Self {
+        self.fill_rule = Some(rule);
+        self
+    }
+
+    /// Sets the [`Triangulation`] algorithm.
+    #[inline]
+    pub fn triangulation(mut self, triangulation: Triangulation) -> Self {
+        self.triangulation = triangulation;
+        self
+    }
+
+    /// Splits triangles until no edge is longer than `max_edge`.
+    ///
+    /// Colors are only evaluated at vertices, so large triangles would flatten gradients.
+    #[inline]
+    pub fn refine(mut self, max_edge: f32) -> Self {
+        self.refine = Some(max_edge);
+        self
+    }
+
+    /// Adds a fringe `width` wide around the outside of every boundary, including holes, fading
+    /// the alpha to 0, such that edges look smooth without multisampling.
+    ///
+    /// `width` should be about the size of a pixel, the fringe extends the shape outwards, so
+    /// it appears `width / 2` larger.
+    #[inline]
+    pub fn antialias(mut self, width: f32) -> Self {
+        self.antialias = Some(width);
+        self
+    }
 }
 
-impl Shape for Fill<Polygon> {
-    type Input = Polygon;
-    type Output = Mesh;
+/// Loops of boundary vertices of the triangles in `mesh`, with the inside on the left.
+///
+/// Boundary edges belong to a single triangle, so the loops run through the vertices of the
+/// triangles as they are, including those added by [`refine`].
+fn boundaries(mesh: &Mesh) -> Vec<Vec<u32>> {
+    let position = |i: u32| mesh.vertices[i as usize].position.truncate();
+
+    // vertices are matched by position, as bridging holes duplicates vertices, such that both
+    // sides of a bridge cancel out
+    let mut first = HashMap::new();
+    let canonical: Vec<u32> = (0..mesh.vertices.len() as u32)
+        .map(|i| {
+            let p = position(i) + Vec2::ZERO;
+
+            *first.entry((p.x.to_bits(), p.y.to_bits())).or_insert(i)
+        })
+        .collect();
+
+    let mut edges = Vec::with_capacity(mesh.indices.len());
+
+    for triangle in mesh.indices.chunks_exact(3) {
+        let (a, b, c) = (triangle[0], triangle[1], triangle[2]);
+        let ccw = (position(b) - position(a)).perp_dot(position(c) - position(a)) > 0.0;
+
+        for (p0, p1) in [(a, b), (b, c), (c, a)].iter().copied() {
+            let (p0, p1) = (canonical[p0 as usize], canonical[p1 as usize]);
+
+            if p0 != p1 {
+                edges.push(if ccw { (p0, p1) } else { (p1, p0) });
+            }
+        }
+    }
+
+    // edges shared by two triangles are traversed in both directions
+    let shared: HashSet<(u32, u32)> = edges.iter().copied().collect();
+    edges.retain(|(a, b)| !shared.contains(&(*b, *a)));
+
+    let mut next: HashMap<u32, Vec<u32>> = HashMap::new();
+
+    for &(a, b) in &edges {
+        next.entry(a).or_default().push(b);
+    }
+
+    let mut loops = Vec::new();
+
+    for &(start, _) in &edges {
+        let mut boundary = Vec::new();
+        let mut vertex = start;
+
+        while let Some(to) = next.get_mut(&vertex).and_then(Vec::pop) {
+            boundary.push(vertex);
+            vertex = to;
+
+            if vertex == start {
+                break;
+            }
+        }
+
+        if boundary.len() > 2 {
+            loops.push(boundary);
+        }
+    }
+
+    loops
+}
+
+/// Appends a strip `width` wide to the outside of every boundary of `mesh`, sharing the
+/// boundary vertices, with the color of the outer vertices fully transparent.
+fn fringe(mesh: &mut Mesh, paint: &impl Paint, width: f32) {
+    for boundary in boundaries(mesh) {
+        let len = boundary.len();
+        let index = mesh.vertices.len() as u32;
+
+        for i in 0..len {
+            let position = |i: usize| mesh.vertices[boundary[i] as usize].position.truncate();
+
+            let p0 = position((i + len - 1) % len);
+            let p1 = position(i);
+            let p2 = position((i + 1) % len);
+
+            let n0 = right(p1 - p0).normalize_or_zero();
+            let n1 = right(p2 - p1).normalize_or_zero();
+            let n = (n0 + n1).normalize_or_zero();
+
+            // miter, limited to avoid spikes at sharp corners
+            let offset = if n == Vec2::ZERO {
+                n0 * width
+            } else {
+                n * width / n.dot(n0).max(0.25)
+            };
+
+            let mut color = paint.color(p1 + offset);
+            color[3] = 0.0;
+
+            mesh.vertices.push(Vertex {
+                position: (p1 + offset).extend(0.0),
+                color,
+                uv: Vec2::ZERO,
+            });
+        }
+
+        for i in 0..len {
+            let j = (i + 1) % len;
+
+            let (a0, o0) = (boundary[i], index + i as u32);
+            let (a1, o1) = (boundary[j], index + j as u32);
+
+            // same winding as the triangulation
+            mesh.indices.extend_from_slice(&[a0, a1, o0, a1, o1, o0]);
+        }
+    }
+}
+
+type Edges = HashMap<(usize, usize), Vec<usize>>;
+
+#[inline]
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+/// Finds the longest edge of triangle `t`, preferring `prefer` on ties.
+#[inline]
+fn longest_edge(
+    points: &[Vec2],
+    indices: &[usize],
+    t: usize,
+    prefer: (usize, usize),
+) -> (usize, usize) {
+    let mut longest = (0, 0);
+    let mut length = -1.0;
+
+    for i in 0..3 {
+        let a = indices[t * 3 + i];
+        let b = indices[t * 3 + (i + 1) % 3];
+
+        let l = points[a].distance_squared(points[b]);
+
+        if l > length || (l == length && edge_key(a, b) == prefer) {
+            longest = edge_key(a, b);
+            length = l;
+        }
+    }
+
+    longest
+}
+
+/// Bisects edge `a`, `b` and every triangle sharing it, returning the affected triangles.
+fn bisect(
+    points: &mut Vec<Vec2>,
+    indices: &mut Vec<usize>,
+    edges: &mut Edges,
+    (a, b): (usize, usize),
+) -> Vec<usize> {
+    let m = points.len();
+    points.push((points[a] + points[b]) / 2.0);
+
+    let mut affected = Vec::new();
+
+    for s in edges.remove(&(a, b)).unwrap_or_default() {
+        let i = (0..3)
+            .find(|i| edge_key(indices[s * 3 + i], indices[s * 3 + (i + 1) % 3]) == (a, b))
+            .unwrap();
+
+        // keeping the winding, [v0, v1, v2] with the edge v0 -> v1 becomes
+        // [v0, m, v2] and [m, v1, v2]
+        let v0 = indices[s * 3 + i];
+        let v1 = indices[s * 3 + (i + 1) % 3];
+        let v2 = indices[s * 3 + (i + 2) % 3];
+
+        let n = indices.len() / 3;
+
+        indices[s * 3] = v0;
+        indices[s * 3 + 1] = m;
+        indices[s * 3 + 2] = v2;
+
+        indices.extend_from_slice(&[m, v1, v2]);
+
+        if let Some(triangles) = edges.get_mut(&edge_key(v1, v2)) {
+            for triangle in triangles.iter_mut().filter(|triangle| **triangle == s) {
+                *triangle = n;
+            }
+        }
+
+        edges.entry(edge_key(v0, m)).or_default().push(s);
+        edges.entry(edge_key(m, v1)).or_default().push(n);
+        edges
+            .entry(edge_key(m, v2))
+            .or_default()
+            .extend_from_slice(&[s, n]);
+
+        affected.push(s);
+        affected.push(n);
+    }
+
+    affected
+}
+
+/// Splits triangles until no edge is longer than `max_edge`.
+///
+/// Uses longest edge bisection, where a triangle is only split along the longest edge of both
+/// triangles sharing it, which keeps triangles well shaped and avoids t-junctions.
+fn refine(points: &mut Vec<Vec2>, indices: &mut Vec<usize>, max_edge: f32) {
+    let mut edges = Edges::new();
+
+    for (t, triangle) in indices.chunks_exact(3).enumerate() {
+        for i in 0..3 {
+            edges
+                .entry(edge_key(triangle[i], triangle[(i + 1) % 3]))
+                .or_default()
+                .push(t);
+        }
+    }
+
+    let max_edge2 = max_edge * max_edge;
+    let mut queue: Vec<usize> = (0..indices.len() / 3).collect();
+
+    while let Some(t) = queue.pop() {
+        let (a, b) = longest_edge(points, indices, t, (usize::MAX, usize::MAX));
+
+        if points[a].distance_squared(points[b]) <= max_edge2 {
+            continue;
+        }
+
+        // follow the path of longest edges until two triangles share their longest edge
+        let mut path = vec![t];
+        let mut edge = (a, b);
+
+        loop {
+            let current = *path.last().unwrap();
+
+            let neighbor = edges[&edge]
+                .iter()
+                .copied()
+                .find(|triangle| *triangle != current);
 
+            match neighbor {
+                Some(neighbor) if !path.contains(&neighbor) => {
+                    let longest = longest_edge(points, indices, neighbor, edge);
+
+                    if longest == edge {
+                        break;
+                    }
+
+                    path.push(neighbor);
+                    edge = longest;
+                }
+                _ => break,
+            }
+        }
+
+        queue.extend(bisect(points, indices, &mut edges, edge));
+        queue.push(t);
+    }
+}
+
+impl<T, P: Paint> Fill<T, P> {
+    /// Triangulates a verified polygon, returning the points and indices.
     #[inline]
-    fn generate(&self, _cfg: &Config, mut polygon: Self::Input) -> Self::Output {
-        polygon.verify();
+    fn triangulate(&self, mut polygon: HoledPolygon) -> Result<(Vec<Vec2>, Vec<usize>), Error> {
+        match self.triangulation {
+            Triangulation::EarClipping => {
+                for hole in polygon.holes {
+                    polygon.polygon.try_merge_hole(hole)?;
+                }
 
-        let indices = polygon
-            .triangulate()
-            .into_iter()
-            .map(|i| i as u32)
-            .collect();
+                let indices = polygon.polygon.try_triangulate()?;
+
+                Ok((polygon.polygon.points, indices))
+            }
+            Triangulation::Delaunay => {
+                let rings: Vec<&[Vec2]> = std::iter::once(&polygon.polygon)
+                    .chain(&polygon.holes)
+                    .map(|ring| ring.points.as_slice())
+                    .collect();
+
+                let indices = delaunay::triangulate(&rings)?;
+
+                let mut points = polygon.polygon.points;
 
-        let vertices = polygon
-            .points
+                for mut hole in polygon.holes {
+                    points.append(&mut hole.points);
+                }
+
+                Ok((points, indices))
+            }
+        }
+    }
+
+    /// Creates mesh from triangulated points, adding a fringe around its boundaries if
+    /// antialiasing.
+    #[inline]
+    fn mesh(&self, mut points: Vec<Vec2>, mut indices: Vec<usize>) -> Mesh {
+        if let Some(max_edge) = self.refine {
+            if !self.paint.is_solid() {
+                refine(&mut points, &mut indices, max_edge);
+            }
+        }
+
+        let vertices = points
             .into_iter()
             .map(|p| Vertex {
                 position: p.extend(0.0),
-                color: self.color,
+                color: self.paint.color(p),
+                uv: Vec2::ZERO,
             })
             .collect();
 
-        Mesh { vertices, indices }
+        let indices = indices.into_iter().map(|i| i as u32).collect();
+
+        let mut mesh = Mesh { vertices, indices };
+
+        if let Some(width) = self.antialias {
+            fringe(&mut mesh, &self.paint, width);
+        }
+
+        mesh
+    }
+}
+
+impl<T, P: Paint> Fill<T, P> {
+    /// Resolves `polygon` into simple polygons, by [`FillRule`] if set.
+    #[inline]
+    fn resolve(&self, polygon: HoledPolygon) -> Result<Vec<HoledPolygon>, Error> {
+        let rule = match self.fill_rule {
+            Some(rule) => rule,
+            // verified when filled
+            None => return Ok(vec![polygon]),
+        };
+
+        let mut groups = vec![vec![polygon.polygon.points.as_slice()]];
+
+        for hole in &polygon.holes {
+            groups.push(vec![hole.points.as_slice()]);
+        }
+
+        for group in &groups {
+            crate::error::finite(group[0])?;
+        }
+
+        let polygons = boolean::resolve(&groups, |windings| {
+            rule.is_inside(windings[0]) && !windings[1..].iter().any(|w| rule.is_inside(*w))
+        });
+
+        Ok(polygons)
+    }
+
+    /// Triangulates and paints `polygons`.
+    fn fill(&self, polygons: impl IntoIterator<Item = HoledPolygon>) -> Result<Mesh, Error> {
+        let mut indices = Vec::new();
+        let mut points = Vec::new();
+
+        for polygon in polygons {
+            for mut polygon in self.resolve(polygon)? {
+                polygon.try_verify()?;
+
+                let (mut polygon_points, polygon_indices) = self.triangulate(polygon)?;
+
+                let index = points.len();
+
+                polygon_indices
+                    .into_iter()
+                    .for_each(|i| indices.push(i + index));
+
+                points.append(&mut polygon_points);
+            }
+        }
+
+        Ok(self.mesh(points, indices))
+    }
+}
+
+impl<P: Paint> Shape for Fill<Polygon, P> {
+    type Input = Polygon;
+    type Output = Mesh;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, polygon: Self::Input) -> Self::Output {
+        self.try_generate(cfg, polygon)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, polygon: Self::Input) -> Result<Self::Output, Error> {
+        self.fill(std::iter::once(polygon.into()))
     }
 }
 
-impl Shape for Fill<HoledPolygon> {
+impl<P: Paint> Shape for Fill<HoledPolygon, P> {
     type Input = HoledPolygon;
     type Output = Mesh;
 
     #[inline]
-    fn generate(&self, _cfg: &Config, mut polygon: Self::Input) -> Self::Output {
-        polygon.verify();
+    fn generate(&self, cfg: &Config, polygon: Self::Input) -> Self::Output {
+        self.try_generate(cfg, polygon)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        for hole in polygon.holes {
-            polygon.polygon.merge_hole(hole);
-        }
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, polygon: Self::Input) -> Result<Self::Output, Error> {
+        self.fill(std::iter::once(polygon))
+    }
+}
 
-        let indices = polygon
-            .polygon
-            .triangulate()
-            .into_iter()
-            .map(|i| i as u32)
-            .collect();
+impl<P: Paint> Shape for Fill<Vec<Polygon>, P> {
+    type Input = Vec<Polygon>;
+    type Output = Mesh;
 
-        let vertices = polygon
-            .polygon
-            .points
-            .into_iter()
-            .map(|p| Vertex {
-                position: p.extend(0.0),
-                color: self.color,
-            })
-            .collect();
+    #[inline]
+    fn generate(&self, cfg: &Config, polygons: Self::Input) -> Self::Output {
+        self.try_generate(cfg, polygons)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        Mesh { vertices, indices }
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, polygons: Self::Input) -> Result<Self::Output, Error> {
+        self.fill(polygons.into_iter().map(Into::into))
     }
 }
 
-impl Shape for Fill<Vec<HoledPolygon>> {
+impl<P: Paint> Shape for Fill<Vec<HoledPolygon>, P> {
     type Input = Vec<HoledPolygon>;
     type Output = Mesh;
 
     #[inline]
-    fn generate(&self, _cfg: &Config, polygons: Self::Input) -> Self::Output {
-        let mut indices = Vec::new();
-        let mut vertices = Vec::new();
-        let mut index = 0;
+    fn generate(&self, cfg: &Config, polygons: Self::Input) -> Self::Output {
+        self.try_generate(cfg, polygons)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        for mut polygon in polygons {
-            polygon.verify();
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, polygons: Self::Input) -> Result<Self::Output, Error> {
+        self.fill(polygons)
+    }
+}
 
-            for hole in polygon.holes {
-                polygon.polygon.merge_hole(hole);
-            }
+#[cfg(test)]
+mod tests {
+    use glam::Vec2;
 
-            polygon
-                .polygon
-                .triangulate()
-                .into_iter()
-                .for_each(|i| indices.push(i as u32 + index));
-
-            polygon.polygon.points.into_iter().for_each(|p| {
-                vertices.push(Vertex {
-                    position: p.extend(0.0),
-                    color: self.color,
-                })
-            });
+    use super::*;
+
+    fn rect(min: Vec2, max: Vec2) -> Polygon {
+        Polygon::from(vec![
+            min,
+            Vec2::new(max.x, min.y),
+            max,
+            Vec2::new(min.x, max.y),
+        ])
+    }
+
+    fn area(mesh: &Mesh) -> f32 {
+        mesh.indices
+            .chunks_exact(3)
+            .map(|t| {
+                let p = |i: u32| mesh.vertices[i as usize].position.truncate();
+
+                (p(t[1]) - p(t[0])).perp_dot(p(t[2]) - p(t[0])).abs() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn fill_rules() {
+        // pentagram drawn in one stroke, crossing itself around a pentagon wound twice
+        let points: Vec<Vec2> = (0..5)
+            .map(|i| {
+                let a = std::f32::consts::FRAC_PI_2 + i as f32 * 4.0 * std::f32::consts::PI / 5.0;
+
+                Vec2::new(a.cos(), a.sin())
+            })
+            .collect();
+        let pentagram = Polygon::from(points);
+
+        let fill = |rule| {
+            Fill::<Polygon, _>::new([1.0; 4])
+                .fill_rule(rule)
+                .generate(&Config::default(), pentagram.clone())
+        };
 
-            index = vertices.len() as u32;
+        // star with tips at 1 and inner corners at cos(72°) / cos(36°), less the pentagon
+        let inner = 72f32.to_radians().cos() / 36f32.to_radians().cos();
+        let star = 5.0 * inner * 36f32.to_radians().sin();
+        let pentagon = 2.5 * inner * inner * 72f32.to_radians().sin();
+
+        assert!((area(&fill(FillRule::NonZero)) - star).abs() < 1e-4);
+        assert!((area(&fill(FillRule::EvenOdd)) - (star - pentagon)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn fringe_outside_holes() {
+        let polygon = HoledPolygon {
+            polygon: rect(Vec2::splat(-1.0), Vec2::splat(1.0)),
+            holes: vec![rect(Vec2::splat(-0.3), Vec2::splat(0.3))],
+        };
+
+        // the fringe adds about width times the length of the boundaries
+        let expected = 4.0 - 0.36 + 0.05 * (8.0 + 2.4);
+
+        for triangulation in [Triangulation::EarClipping, Triangulation::Delaunay].iter() {
+            let mesh = Fill::<HoledPolygon, _>::new([1.0; 4])
+                .triangulation(*triangulation)
+                .antialias(0.05)
+                .generate(&Config::default(), polygon.clone());
+
+            for vertex in mesh.vertices.iter().filter(|v| v.color[3] == 0.0) {
+                let p = vertex.position.truncate().abs();
+
+                // either outside the square or inside the hole
+                assert!(p.max_element() > 1.0 || p.max_element() < 0.3, "{:?}", p);
+            }
+
+            assert!((area(&mesh) - expected).abs() < 0.01, "{}", area(&mesh));
         }
+    }
+
+    #[test]
+    fn fringe_shares_refined_vertices() {
+        let paint = |p: Vec2| [p.x, p.y, 1.0, 1.0];
+        let square = rect(Vec2::ZERO, Vec2::ONE);
+
+        let fill = Fill::<Polygon, _>::new(paint).refine(0.1);
+        let solid = fill.generate(&Config::default(), square.clone());
+        let mesh = fill.antialias(0.01).generate(&Config::default(), square);
+
+        // only transparent vertices are added, one for every vertex on the boundary
+        let added = &mesh.vertices[solid.vertices.len()..];
 
-        Mesh { vertices, indices }
+        assert!(added.iter().all(|v| v.color[3] == 0.0));
+        // bisecting the sides until no edge is longer than 0.1 splits them into 16
+        assert_eq!(added.len(), 64);
+        assert!((area(&mesh) - 1.02 * 1.02).abs() < 1e-4);
     }
 }
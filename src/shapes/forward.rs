@@ -1,4 +1,4 @@
-use crate::{Polyline, Shape};
+use crate::{Error, Polyline, Shape};
 
 
 #[derive(Clone, Debug)]
@@ -11,9 +11,17 @@ impl Shape for Forward {
     type Output = Polyline;
 
 	#[inline]
-    fn generate(&self, _cfg: &crate::Config, mut input: Self::Input) -> Self::Output {
-		input.push(input.points[input.points.len() - 1] + input.direction() * self.length);	
-
-		input
+    fn generate(&self, cfg: &crate::Config, input: Self::Input) -> Self::Output {
+		self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
     }
-}
\ No newline at end of file
+
+	#[inline]
+	fn try_generate(&self, _cfg: &crate::Config, mut input: Self::Input) -> Result<Self::Output, Error> {
+		let direction = input.try_direction()?;
+		let last = *input.points.last().ok_or(Error::Degenerate("polyline has no points"))?;
+
+		input.push(last + direction * self.length);
+
+		Ok(input)
+	}
+}
@@ -1,4 +1,4 @@
-use crate::{holed_polygon::HoledPolygon, polygon::Polygon, Config, Shape};
+use crate::{error::Error, holed_polygon::HoledPolygon, polygon::Polygon, Config, Shape};
 
 #[derive(Clone, Debug)]
 pub struct Hole<H: Shape<Output = Polygon>> {
@@ -19,4 +19,12 @@ where
             holes: vec![self.hole.generate(cfg, ())],
         }
     }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polygon: Self::Input) -> Result<Self::Output, Error> {
+        Ok(HoledPolygon {
+            polygon,
+            holes: vec![self.hole.try_generate(cfg, ())?],
+        })
+    }
 }
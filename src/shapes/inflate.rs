@@ -0,0 +1,195 @@
+use glam::Vec2;
+
+use crate::{boolean, error::Error, holed_polygon::HoledPolygon, polygon::Polygon, Config, Shape};
+
+use super::{
+    thicken::{push_join, right},
+    LineJoin,
+};
+
+/// Offsets `points` to the right by `distance`, or to the left if it's negative, without
+/// resolving intersections, see [`push_join`].
+fn offset_ring(cfg: &Config, points: &[Vec2], distance: f32, join: LineJoin) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+
+    // the left side is the right side going the other way
+    if distance < 0.0 {
+        points.reverse();
+    }
+
+    let mut ring = Polygon::with_capacity(points.len() * 2);
+    let len = points.len();
+
+    for i in 0..len {
+        let p0 = points[(i + len - 1) % len];
+        let p1 = points[i];
+        let p2 = points[(i + 1) % len];
+
+        let n0 = right((p1 - p0).normalize());
+        let n1 = right((p2 - p1).normalize());
+
+        // inner corners are connected through the point, edges shortened from both ends could
+        // otherwise turn around
+        push_join(&mut ring, cfg, p1, (n0, n1), distance.abs(), join, 0.0);
+    }
+
+    if distance < 0.0 {
+        ring.points.reverse();
+    }
+
+    ring.points
+}
+
+/// Offsets every ring of `polygon` outwards by `distance`, with holes wound clockwise.
+///
+/// The region with a positive winding number is `polygon` inflated by `distance`.
+pub(super) fn offset(
+    cfg: &Config,
+    polygon: &HoledPolygon,
+    distance: f32,
+    join: LineJoin,
+) -> Vec<Vec<Vec2>> {
+    let mut rings = vec![offset_ring(cfg, &polygon.polygon.points, distance, join)];
+
+    for hole in &polygon.holes {
+        let mut points = hole.points.clone();
+        points.reverse();
+
+        rings.push(offset_ring(cfg, &points, distance, join));
+    }
+
+    rings
+}
+
+/// Grows a polygon by moving its boundary outwards by `distance`, or shrinks it if `distance`
+/// is negative.
+///
+/// Parts that collide are merged and parts that collapse are removed, which is why the result
+/// may consist of any number of polygons.
+#[derive(Clone, Debug)]
+pub struct Inflate<T> {
+    pub distance: f32,
+    pub join: LineJoin,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> Inflate<T> {
+    #[inline]
+    pub const fn new(distance: f32, join: LineJoin) -> Self {
+        Self {
+            distance,
+            join,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn inflate(&self, cfg: &Config, mut input: HoledPolygon) -> Result<Vec<HoledPolygon>, Error> {
+        input.try_verify()?;
+
+        if !self.distance.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        let rings = offset(cfg, &input, self.distance, self.join);
+        let rings: Vec<&[Vec2]> = rings.iter().map(Vec::as_slice).collect();
+
+        Ok(boolean::resolve(&[rings], |windings| windings[0] > 0))
+    }
+}
+
+impl Shape for Inflate<Polygon> {
+    type Input = Polygon;
+    type Output = Vec<HoledPolygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.inflate(cfg, HoledPolygon::from(input))
+    }
+}
+
+impl Shape for Inflate<HoledPolygon> {
+    type Input = HoledPolygon;
+    type Output = Vec<HoledPolygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.inflate(cfg, input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    fn square(min: f32, max: f32) -> Polygon {
+        Polygon::from(vec![
+            Vec2::new(min, min),
+            Vec2::new(max, min),
+            Vec2::new(max, max),
+            Vec2::new(min, max),
+        ])
+    }
+
+    fn area(polygons: &[HoledPolygon]) -> f32 {
+        polygons
+            .iter()
+            .map(|p| p.polygon.area() - p.holes.iter().map(Polygon::area).sum::<f32>())
+            .sum()
+    }
+
+    #[test]
+    fn joins() {
+        let cfg = Config::new(0.001);
+        let inflate = |distance, join| Inflate::<Polygon>::new(distance, join);
+
+        let miter = inflate(0.5, LineJoin::Miter { limit: 4.0 }).generate(&cfg, square(0.0, 2.0));
+        assert!((area(&miter) - 9.0).abs() < 1e-4, "{}", area(&miter));
+
+        // corners cut off by half of the 0.5 by 0.5 squares
+        let bevel = inflate(0.5, LineJoin::Bevel).generate(&cfg, square(0.0, 2.0));
+        assert!((area(&bevel) - 8.5).abs() < 1e-4, "{}", area(&bevel));
+
+        // quarter circles in the corners
+        let round = inflate(0.5, LineJoin::Round).generate(&cfg, square(0.0, 2.0));
+        let expected = 4.0 + 4.0 * 2.0 * 0.5 + PI * 0.25;
+        assert!((area(&round) - expected).abs() < 1e-3, "{}", area(&round));
+    }
+
+    #[test]
+    fn deflate() {
+        let cfg = Config::default();
+        let join = LineJoin::default();
+
+        let deflated = Inflate::<Polygon>::new(-0.5, join).generate(&cfg, square(0.0, 2.0));
+        assert!((area(&deflated) - 1.0).abs() < 1e-4);
+
+        // collapsing entirely
+        let collapsed = Inflate::<Polygon>::new(-1.5, join).generate(&cfg, square(0.0, 2.0));
+        assert!(collapsed.is_empty());
+
+        // holes grow as the polygon shrinks
+        let polygon = HoledPolygon {
+            polygon: square(0.0, 4.0),
+            holes: vec![square(1.5, 2.5)],
+        };
+
+        let deflated = Inflate::<HoledPolygon>::new(-0.25, join).generate(&cfg, polygon);
+        assert_eq!(deflated.len(), 1);
+        assert!((area(&deflated) - (3.5 * 3.5 - 1.5 * 1.5)).abs() < 1e-4);
+    }
+}
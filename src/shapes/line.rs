@@ -1,6 +1,7 @@
 use glam::Vec2;
 
 use crate::{
+    error::Error,
     polyline::Polyline,
     shape::{Config, Shape},
 };
@@ -26,4 +27,11 @@ impl Shape for Line {
     fn generate(&self, _cfg: &Config, _: Self::Input) -> Self::Output {
         Polyline::from(vec![self.p0, self.p1])
     }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&[self.p0, self.p1])?;
+
+        Ok(self.generate(cfg, input))
+    }
 }
@@ -1,6 +1,6 @@
 use glam::Vec2;
 
-use crate::{polygon::Polygon, Shape};
+use crate::{error::Error, polygon::Polygon, Shape};
 
 #[derive(Clone, Debug)]
 pub struct MapPolygon<F: Fn(&mut Vec2)> {
@@ -21,4 +21,18 @@ impl<F: Fn(&mut Vec2)> Shape for MapPolygon<F> {
 
         polygon
     }
+
+    /// Returns an error if a mapped point isn't finite.
+    #[inline]
+    fn try_generate(
+        &self,
+        cfg: &crate::Config,
+        polygon: Self::Input,
+    ) -> Result<Self::Output, Error> {
+        let polygon = self.generate(cfg, polygon);
+
+        crate::error::finite(&polygon.points)?;
+
+        Ok(polygon)
+    }
 }
@@ -1,35 +1,254 @@
 //! A collection of standard [Shapes][`crate::Shape`].
 
+mod annulus;
+mod arc;
+mod bezier;
+mod boolean;
+mod capsule;
+mod catmull_rom;
+mod chamfer;
 mod circle;
 mod combine;
 mod complete;
+mod convex_decompose;
+mod convex_hull;
+mod dash;
+mod ellipse;
 mod fill;
 mod hole;
 mod id;
+mod inflate;
 mod line;
 mod map;
 mod outline;
 mod parametric;
 mod rect;
+mod regular_polygon;
+mod resample;
+mod round_corners;
+mod rounded_rect;
+mod simplify;
+mod smooth;
 mod split;
+mod star;
+mod svg_path;
 mod thicken;
+mod transform;
+mod trim;
 mod turn;
+mod uv;
+mod variable_thicken;
 mod offset;
 mod forward;
 
+pub use annulus::Annulus;
+pub use arc::{Arc, Pie};
+pub use bezier::{CubicTo, QuadTo};
+pub use boolean::{Boolean, BooleanOp};
+pub use capsule::Capsule;
+pub use catmull_rom::CatmullRom;
+pub use chamfer::Chamfer;
 pub use circle::Circle;
 pub use combine::{Combine, CombineMesh, CombineMeshes};
 pub use complete::Complete;
-pub use fill::Fill;
+pub use convex_decompose::{ConvexDecompose, Decomposition};
+pub use convex_hull::ConvexHull;
+pub use dash::Dash;
+pub use ellipse::Ellipse;
+pub use fill::{Fill, FillRule, Triangulation};
 pub use hole::Hole;
 pub use id::Id;
+pub use inflate::Inflate;
 pub use line::Line;
 pub use map::MapPolygon;
 pub use outline::Outline;
 pub use parametric::Parametric;
 pub use rect::Rect;
+pub use regular_polygon::RegularPolygon;
+pub use resample::Resample;
+pub use round_corners::RoundCorners;
+pub use rounded_rect::RoundedRect;
+pub use simplify::{Algorithm, Simplify};
+pub use smooth::Smooth;
 pub use split::Split;
-pub use thicken::Thicken;
+pub use star::Star;
+pub use svg_path::{SvgPath, SvgPathError};
+pub use thicken::{LineCap, LineJoin, Thicken};
+pub use transform::Transform;
+pub use trim::Trim;
 pub use turn::Turn;
+pub use uv::{StrokeUv, Uv, UvMapping};
+pub use variable_thicken::{VariableThicken, Width};
 pub use offset::Offset;
-pub use forward::Forward;
\ No newline at end of file
+pub use forward::Forward;
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{PI, TAU};
+
+    use super::*;
+    use crate::{error::Error, polygon::Polygon, Config, Shape};
+
+    /// Checks that `polygon` has points and that the flags set on it hold.
+    fn assert_flags(polygon: &Polygon) {
+        let points = &polygon.points;
+        let n = points.len();
+
+        assert!(n >= 3);
+
+        if polygon.is_simple {
+            assert!(polygon.intersections().is_empty());
+        }
+
+        if let Some(is_ccw) = polygon.is_ccw {
+            assert_eq!(polygon.area() > 0.0, is_ccw);
+        }
+
+        if let Some(is_convex) = polygon.is_convex {
+            let sign = polygon.area().signum();
+            let convex = (0..n).all(|i| {
+                let (p0, p1, p2) = (points[i], points[(i + 1) % n], points[(i + 2) % n]);
+                let (d0, d1) = ((p1 - p0).normalize(), (p2 - p1).normalize());
+
+                d0.perp_dot(d1) * sign >= -1e-5
+            });
+
+            assert_eq!(convex, is_convex);
+        }
+    }
+
+    fn assert_area(polygon: &Polygon, expected: f32) {
+        let area = polygon.area();
+
+        assert!((area - expected).abs() < expected * 1e-3, "{}", area);
+    }
+
+    #[test]
+    fn round() {
+        let cfg = Config::new(0.01);
+
+        let circle = Circle::new(2.0).generate(&cfg, ());
+        assert_flags(&circle);
+        assert_area(&circle, PI * 4.0);
+
+        let ellipse = Ellipse::new((2.0, 0.5)).generate(&cfg, ());
+        assert_flags(&ellipse);
+        assert_area(&ellipse, PI);
+
+        for &(end, convex) in [(PI / 2.0, true), (PI, true), (PI * 1.5, false)].iter() {
+            let pie = Pie::new(1.0, 0.0, end).generate(&cfg, ());
+            assert_flags(&pie);
+            assert_eq!(pie.is_convex, Some(convex));
+            assert_area(&pie, end / 2.0);
+        }
+
+        // reversed angles and full circles
+        assert_area(&Pie::new(1.0, PI, 0.0).generate(&cfg, ()), PI / 2.0);
+        assert_flags(&Pie::new(1.0, 0.0, TAU).generate(&cfg, ()));
+
+        assert!(Circle::new(0.0).try_generate(&cfg, ()).is_err());
+        assert!(Pie::new(1.0, 1.0, 1.0).try_generate(&cfg, ()).is_err());
+    }
+
+    #[test]
+    fn rects() {
+        let cfg = Config::new(0.01);
+
+        let rect = Rect::new(2.0, 1.0).generate(&cfg, ());
+        assert_flags(&rect);
+        assert_area(&rect, 2.0);
+
+        for &radius in [0.0, 0.25, 0.5, 2.0].iter() {
+            let rect = RoundedRect::new(2.0, 1.0, radius).generate(&cfg, ());
+            let radius = radius.min(0.5);
+
+            assert_flags(&rect);
+            assert_area(&rect, 2.0 - (4.0 - PI) * radius * radius);
+        }
+
+        let result = Rect::new(-1.0, 1.0).try_generate(&cfg, ());
+        assert!(matches!(result, Err(Error::Degenerate(_))));
+    }
+
+    #[test]
+    fn regular_polygon() {
+        let cfg = Config::default();
+
+        for sides in 3..8 {
+            let polygon = RegularPolygon::new(sides, 1.0).generate(&cfg, ());
+            let expected = sides as f32 / 2.0 * (TAU / sides as f32).sin();
+
+            assert_flags(&polygon);
+            assert_area(&polygon, expected);
+        }
+
+        // too few sides
+        let polygon = RegularPolygon::new(2, 1.0);
+
+        assert!(polygon.generate(&cfg, ()).points.is_empty());
+        assert!(polygon.try_generate(&cfg, ()).is_err());
+    }
+
+    #[test]
+    fn star() {
+        let cfg = Config::default();
+
+        for &points in [2, 3, 5, 8].iter() {
+            for &inner in [0.2, 0.5, 0.9, 1.0, 1.5].iter() {
+                let star = Star::new(points, 1.0, inner).generate(&cfg, ());
+
+                assert_eq!(star.points.len(), points * 2);
+                assert_flags(&star);
+            }
+        }
+
+        // no tips to place corners between
+        for &points in [0, 1].iter() {
+            let star = Star::new(points, 1.0, 0.5);
+
+            assert!(star.generate(&cfg, ()).points.is_empty());
+            assert!(star.try_generate(&cfg, ()).is_err());
+        }
+    }
+
+    #[test]
+    fn capsule() {
+        let cfg = Config::new(0.01);
+
+        let capsule = Capsule::new(2.0, 0.5).generate(&cfg, ());
+        assert_flags(&capsule);
+        assert_area(&capsule, 2.0 + PI * 0.25);
+
+        // a negative length is a circle, not crossing itself
+        let capsule = Capsule::new(-1.0, 0.5).generate(&cfg, ());
+        assert_flags(&capsule);
+        assert_area(&capsule, PI * 0.25);
+    }
+
+    #[test]
+    fn annulus() {
+        let cfg = Config::new(0.01);
+
+        let ring = Annulus::new(0.5, 1.0).generate(&cfg, ());
+        assert_flags(&ring.polygon);
+        assert_eq!(ring.holes.len(), 1);
+        assert_flags(&ring.holes[0]);
+        assert!((ring.area() - PI * 0.75).abs() < 1e-2);
+
+        assert!(Annulus::new(0.0, 1.0).generate(&cfg, ()).holes.is_empty());
+
+        for &(inner, end) in [(0.5, PI / 2.0), (0.5, PI * 1.5), (0.0, PI / 2.0)].iter() {
+            let sector = Annulus::new(inner, 1.0).sector(0.0, end).generate(&cfg, ());
+
+            assert!(sector.holes.is_empty());
+            assert_flags(&sector.polygon);
+            assert_area(&sector.polygon, end / 2.0 * (1.0 - inner * inner));
+        }
+
+        assert!(Annulus::new(1.0, 0.5).try_generate(&cfg, ()).is_err());
+        assert!(Annulus::new(0.5, 1.0)
+            .sector(1.0, 1.0)
+            .try_generate(&cfg, ())
+            .is_err());
+    }
+}
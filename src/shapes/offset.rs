@@ -1,6 +1,6 @@
 use glam::{Vec2};
 
-use crate::{Polyline, Shape};
+use crate::{Error, Polyline, Shape};
 
 #[derive(Clone, Debug)]
 pub struct Offset<T> {
@@ -23,7 +23,16 @@ impl Shape for Offset<Polyline> {
     type Output = Polyline;
 
 	#[inline]
-    fn generate(&self, _cfg: &crate::Config, input: Self::Input) -> Self::Output {	
+    fn generate(&self, cfg: &crate::Config, input: Self::Input) -> Self::Output {
+		self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+	}
+
+	#[inline]
+    fn try_generate(&self, _cfg: &crate::Config, input: Self::Input) -> Result<Self::Output, Error> {
+		if input.points.len() < 2 {
+			return Err(Error::Degenerate("polyline must have at least two points"));
+		}
+
 		#[inline]
         fn nor(v: Vec2) -> Vec2 {
             Vec2::new(-v.y, v.x)
@@ -62,6 +71,6 @@ impl Shape for Offset<Polyline> {
 			line.push(p0 + n * self.offset);
 		}
 
-		line
+		Ok(line)
     }
 }
\ No newline at end of file
@@ -1,41 +1,27 @@
 use glam::Vec2;
 
-use crate::{holed_polygon::HoledPolygon, polygon::Polygon, Config, Shape};
-
-#[inline]
-fn nor(v: Vec2) -> Vec2 {
-    Vec2::new(-v.y, v.x)
-}
-
-#[inline]
-fn offset(polygon: &Polygon, thickness: f32) -> Polygon {
-    let mut offset = Polygon::with_capacity(polygon.points.len());
-
-    let len = polygon.points.len();
-    for i in 1..=len {
-        let p0 = polygon.points[i - 1];
-        let p1 = polygon.points[i % len];
-        let p2 = polygon.points[(i + 1) % len];
-
-        let n0 = nor((p1 - p0).normalize());
-        let n1 = nor((p2 - p1).normalize());
-
-        let n = ((n0 + n1) / 2.0).normalize();
-
-        let t = thickness / n0.dot(n);
-
-        let p = p1 + n * t;
-
-        offset.push(p);
-    }
-
-    offset
-}
-
-/// Outlines a [`Shape`].
+use crate::{boolean, error::Error, holed_polygon::HoledPolygon, polygon::Polygon, Config, Shape};
+
+use super::{inflate, LineJoin};
+
+/// Outlines a [`Shape`], the outline is centered on the boundary, see
+/// [`Inflate`](super::Inflate).
+///
+/// For smooth edges, see [`Outline::antialias`].
+///
+/// # Example
+/// ```
+/// # use scissor::prelude::*;
+/// let ring = Circle::new(1.0)
+///     .outline_with(Outline::new(0.1).antialias(0.01))
+///     .fill_with(Fill::new([1.0; 4]).antialias(0.01));
+/// ```
 #[derive(Clone, Debug)]
 pub struct Outline<T> {
     pub thickness: f32,
+    pub join: LineJoin,
+    /// Width of the antialiasing fringe, see [`Outline::antialias`].
+    pub antialias: Option<f32>,
     _marker: std::marker::PhantomData<*const T>,
 }
 
@@ -44,32 +30,69 @@ impl<T> Outline<T> {
     pub const fn new(thickness: f32) -> Self {
         Self {
             thickness,
+            join: LineJoin::Miter { limit: 4.0 },
+            antialias: None,
             _marker: std::marker::PhantomData,
         }
     }
-}
 
-impl Shape for Outline<Polygon> {
-    type Input = Polygon;
-    type Output = HoledPolygon;
+    /// Sets the [`LineJoin`] of the corners.
+    #[inline]
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Antialiases the outline with a fringe `width` wide on both of its sides, which is added
+    /// when filling it with the same width, see [`Fill::antialias`](super::Fill::antialias).
+    ///
+    /// The fringe makes a filled shape appear `width / 2` larger on every side, so the outline
+    /// is made thinner by `width` to keep appearing [`Outline::thickness`] thick.
+    #[inline]
+    pub fn antialias(mut self, width: f32) -> Self {
+        self.antialias = Some(width);
+        self
+    }
 
+    /// The region between the polygon inflated and deflated by half the thickness.
     #[inline]
-    fn generate(&self, _cfg: &Config, mut input: Self::Input) -> Self::Output {
-        input.verify();
+    fn outline(&self, cfg: &Config, mut input: HoledPolygon) -> Result<Vec<HoledPolygon>, Error> {
+        input.try_verify()?;
 
-        let hole = offset(&input, self.thickness / 2.0);
-        let mut polygon = offset(&input, -self.thickness / 2.0);
+        let width = self.antialias.unwrap_or(0.0);
 
-        if input.is_convex() {
-            polygon.is_simple = true;
-            polygon.is_ccw = Some(true);
-            polygon.is_convex = Some(true);
+        if !self.thickness.is_finite() || !width.is_finite() {
+            return Err(Error::NonFinite);
         }
 
-        HoledPolygon {
-            polygon,
-            holes: vec![hole],
-        }
+        // the fringes on both sides are half visible
+        let half = (self.thickness - width).max(0.0) / 2.0;
+
+        let outer = inflate::offset(cfg, &input, half, self.join);
+        let inner = inflate::offset(cfg, &input, -half, self.join);
+
+        let outer: Vec<&[Vec2]> = outer.iter().map(Vec::as_slice).collect();
+        let inner: Vec<&[Vec2]> = inner.iter().map(Vec::as_slice).collect();
+
+        Ok(boolean::resolve(&[outer, inner], |windings| {
+            windings[0] > 0 && windings[1] <= 0
+        }))
+    }
+}
+
+impl Shape for Outline<Polygon> {
+    type Input = Polygon;
+    type Output = Vec<HoledPolygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.outline(cfg, HoledPolygon::from(input))
     }
 }
 
@@ -78,41 +101,69 @@ impl Shape for Outline<HoledPolygon> {
     type Output = Vec<HoledPolygon>;
 
     #[inline]
-    fn generate(&self, _cfg: &Config, mut input: Self::Input) -> Self::Output {
-        input.verify();
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.outline(cfg, input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Polygon {
+        Polygon::from(vec![
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(1.0, -1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(-1.0, 1.0),
+        ])
+    }
 
-        let mut polygons = Vec::with_capacity(input.holes.len() + 1);
+    fn area(polygons: &[HoledPolygon]) -> f32 {
+        polygons
+            .iter()
+            .map(|p| p.polygon.area() - p.holes.iter().map(Polygon::area).sum::<f32>())
+            .sum()
+    }
 
-        for hole in input.holes {
-            let hole = offset(&hole, self.thickness);
-            let mut polygon = offset(&hole, -self.thickness);
+    #[test]
+    fn antialias() {
+        let cfg = Config::default();
 
-            if input.polygon.is_convex() {
-                polygon.is_simple = true;
-                polygon.is_convex = Some(true);
-                polygon.is_ccw = Some(true);
-            }
+        let outline = Outline::<Polygon>::new(0.2).generate(&cfg, square());
+        assert!((area(&outline) - (2.2 * 2.2 - 1.8 * 1.8)).abs() < 1e-4);
 
-            polygons.push(HoledPolygon {
-                polygon,
-                holes: vec![hole],
-            });
-        }
+        // half of the fringes on both sides make up the rest
+        let outline = Outline::<Polygon>::new(0.2)
+            .antialias(0.1)
+            .generate(&cfg, square());
+        assert!((area(&outline) - (2.1 * 2.1 - 1.9 * 1.9)).abs() < 1e-4);
 
-        let hole = offset(&input.polygon, self.thickness / 2.0);
-        let mut polygon = offset(&input.polygon, -self.thickness / 2.0);
+        // thinner than the fringe
+        let outline = Outline::<Polygon>::new(0.05)
+            .antialias(0.1)
+            .generate(&cfg, square());
+        assert!(outline.is_empty());
+    }
 
-        if input.polygon.is_convex() {
-            polygon.is_simple = true;
-            polygon.is_convex = Some(true);
-            polygon.is_ccw = Some(true);
-        }
+    #[test]
+    fn round() {
+        let cfg = Config::new(0.001);
 
-        polygons.push(HoledPolygon {
-            polygon,
-            holes: vec![hole],
-        });
+        // rounded outside corners, the inside ones stay sharp
+        let outline = Outline::<Polygon>::new(0.2)
+            .join(LineJoin::Round)
+            .generate(&cfg, square());
+        let expected = 4.0 + 4.0 * 2.0 * 0.1 + std::f32::consts::PI * 0.01 - 1.8 * 1.8;
 
-        polygons
+        assert_eq!(outline.len(), 1);
+        assert_eq!(outline[0].holes.len(), 1);
+        assert!((area(&outline) - expected).abs() < 1e-4);
     }
 }
@@ -2,8 +2,43 @@ use std::ops::Range;
 
 use glam::Vec2;
 
-use crate::{polyline::Polyline, Config, Shape};
+use crate::{error::Error, polyline::Polyline, shape::Tolerance, Config, Shape};
 
+/// Maximum number of times an interval is halved with a [`Tolerance`].
+const MAX_DEPTH: u32 = 16;
+
+/// Number of intervals sampled before subdividing, such that features smaller than a single
+/// interval aren't missed.
+const INITIAL_STEPS: usize = 16;
+
+/// Samples a parametric curve over `interval`.
+///
+/// Points are spaced [`Config::resolution`] apart in parameter space, or placed adaptively where
+/// the curve bends when [`Config::tolerance`] is set. The end of `interval` is left out in both
+/// cases, such that closed curves don't repeat their first point.
+///
+/// # Example
+/// ```
+/// # use std::f32::consts::{PI, SQRT_2, TAU};
+/// # use scissor::{prelude::*, Config};
+/// // the logo, flattened adaptively
+/// let shape = Parametric::new(|x| Vec2::new(x.sin(), -x.cos()), 0.0..TAU)
+///     .complete()
+///     .map(|v| {
+///         if v.y < -(PI / 4.0).sin() {
+///             v.y = v.x.abs() - SQRT_2;
+///         }
+///     })
+///     .hole(Circle::new(0.4))
+///     .split(
+///         |shape| shape.fill([0.1, 0.2, 0.6, 1.0]),
+///         |shape| shape.outline(0.1).fill([0.0, 0.0, 0.0, 1.0]),
+///     )
+///     .combine();
+///
+/// let mesh = shape.generate(&Config::new(0.05).tolerance(0.001, 0.2), ());
+/// assert!(!mesh.indices.is_empty());
+/// ```
 #[derive(Clone, Debug)]
 pub struct Parametric<F: Fn(f32) -> Vec2> {
     pub f: F,
@@ -17,12 +52,71 @@ impl<F: Fn(f32) -> Vec2> Parametric<F> {
     }
 }
 
+impl<F: Fn(f32) -> Vec2> Parametric<F> {
+    /// Subdivides the interval from `t0` to `t1` until it's within `tolerance`, pushing every
+    /// point but the one at `t0`.
+    fn subdivide(
+        &self,
+        polyline: &mut Polyline,
+        tolerance: &Tolerance,
+        (t0, p0): (f32, Vec2),
+        (t1, p1): (f32, Vec2),
+        depth: u32,
+    ) {
+        let tm = (t0 + t1) / 2.0;
+        let pm = (self.f)(tm);
+
+        let chord = p1 - p0;
+        let deviation = if chord == Vec2::ZERO {
+            pm.distance(p0)
+        } else {
+            chord.perp_dot(pm - p0).abs() / chord.length()
+        };
+
+        let d0 = pm - p0;
+        let d1 = p1 - pm;
+        let angle = d0.perp_dot(d1).atan2(d0.dot(d1)).abs();
+
+        if depth < MAX_DEPTH && (deviation > tolerance.deviation || angle > tolerance.angle) {
+            self.subdivide(polyline, tolerance, (t0, p0), (tm, pm), depth + 1);
+            self.subdivide(polyline, tolerance, (tm, pm), (t1, p1), depth + 1);
+        } else {
+            polyline.push(p1);
+        }
+    }
+}
+
 impl<F: Fn(f32) -> Vec2> Shape for Parametric<F> {
     type Input = ();
     type Output = Polyline;
 
     #[inline]
     fn generate(&self, cfg: &Config, _: Self::Input) -> Self::Output {
+        if let Some(tolerance) = &cfg.tolerance {
+            let dist = self.interval.end - self.interval.start;
+
+            let mut t0 = self.interval.start;
+            let mut p0 = (self.f)(t0);
+
+            let mut polyline = Polyline::default();
+            polyline.push(p0);
+
+            for step in 1..=INITIAL_STEPS {
+                let t1 = step as f32 / INITIAL_STEPS as f32 * dist + self.interval.start;
+                let p1 = (self.f)(t1);
+
+                self.subdivide(&mut polyline, tolerance, (t0, p0), (t1, p1), 0);
+
+                t0 = t1;
+                p0 = p1;
+            }
+
+            // the end is left out like when spacing by resolution
+            polyline.points.pop();
+
+            return polyline;
+        }
+
         let dist = self.interval.end - self.interval.start;
         let steps = (dist / cfg.resolution).ceil() as usize;
 
@@ -38,4 +132,64 @@ impl<F: Fn(f32) -> Vec2> Shape for Parametric<F> {
 
         polyline
     }
+
+    /// Returns an error if the interval or a sampled point isn't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        if !self.interval.start.is_finite() || !self.interval.end.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        let polyline = self.generate(cfg, input);
+
+        crate::error::finite(&polyline.points)?;
+
+        Ok(polyline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::TAU;
+
+    use super::*;
+
+    fn circle() -> Parametric<impl Fn(f32) -> Vec2> {
+        Parametric::new(|t: f32| Vec2::new(t.cos(), t.sin()), 0.0..TAU)
+    }
+
+    #[test]
+    fn tolerance() {
+        let cfg = Config::default().tolerance(0.001, 1.0);
+        let points = circle().generate(&cfg, ()).points;
+
+        // the end isn't repeated
+        assert!(points[points.len() - 1].distance(points[0]) > 0.01);
+
+        // every chord, including the closing one, stays within the tolerance
+        for i in 0..points.len() {
+            let mid = (points[i] + points[(i + 1) % points.len()]) / 2.0;
+
+            assert!(1.0 - mid.length() <= 0.001);
+        }
+
+        // a finer tolerance places more points
+        let finer = circle().generate(&Config::default().tolerance(0.0001, 1.0), ());
+        assert!(finer.points.len() > points.len());
+    }
+
+    #[test]
+    fn adaptive() {
+        // straight until x = 1, bending after
+        let curve = Parametric::new(|t: f32| Vec2::new(t, (t - 1.0).max(0.0).powi(2)), 0.0..2.0);
+        let points = curve
+            .generate(&Config::default().tolerance(0.001, 1.0), ())
+            .points;
+
+        // the straight half keeps only the initial samples
+        let straight = points.iter().filter(|p| p.x < 1.0).count();
+
+        assert_eq!(straight, INITIAL_STEPS / 2);
+        assert!(points.len() - straight > straight);
+    }
 }
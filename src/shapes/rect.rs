@@ -1,6 +1,6 @@
 use glam::Vec2;
 
-use crate::{polygon::Polygon, Shape};
+use crate::{error::Error, polygon::Polygon, Shape};
 
 #[derive(Clone, Debug)]
 pub struct Rect {
@@ -8,6 +8,13 @@ pub struct Rect {
     pub height: f32,
 }
 
+impl Rect {
+    #[inline]
+    pub fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+}
+
 impl Shape for Rect {
     type Input = ();
     type Output = Polygon;
@@ -26,4 +33,13 @@ impl Shape for Rect {
 
         polygon
     }
+
+    /// Returns an error if the width or height isn't positive.
+    #[inline]
+    fn try_generate(&self, cfg: &crate::Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::positive(self.width, "width must be positive")?;
+        crate::error::positive(self.height, "height must be positive")?;
+
+        Ok(self.generate(cfg, input))
+    }
 }
@@ -0,0 +1,66 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use glam::Vec2;
+
+use crate::{error::Error, polygon::Polygon, Config, Shape};
+
+/// Creates a regular polygon with `sides` corners at `radius`, with the first corner pointing up.
+///
+/// Fewer than three `sides` result in an empty polygon, otherwise the resulting polygon will
+/// always be:
+/// * Simple
+/// * Convex
+/// * CCW
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegularPolygon {
+    pub sides: usize,
+    pub radius: f32,
+}
+
+impl RegularPolygon {
+    #[inline]
+    pub fn new(sides: usize, radius: f32) -> Self {
+        Self { sides, radius }
+    }
+}
+
+impl Shape for RegularPolygon {
+    type Input = ();
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, _cfg: &Config, _: Self::Input) -> Self::Output {
+        if self.sides < 3 {
+            return Polygon::default();
+        }
+
+        let mut polygon = Polygon::with_capacity(self.sides);
+
+        for side in 0..self.sides {
+            let a = FRAC_PI_2 + side as f32 / self.sides as f32 * TAU;
+
+            polygon.push(Vec2::new(a.cos(), a.sin()) * self.radius);
+        }
+
+        polygon.is_ccw = Some(true);
+        polygon.is_convex = Some(true);
+        polygon.is_simple = true;
+
+        polygon
+    }
+
+    /// Returns an error instead of an empty polygon for fewer than three sides, or if the
+    /// radius isn't positive.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        if self.sides < 3 {
+            return Err(Error::Degenerate(
+                "regular polygon must have at least three sides",
+            ));
+        }
+
+        crate::error::positive(self.radius, "radius must be positive")?;
+
+        Ok(self.generate(cfg, input))
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{error::Error, polygon::Polygon, polyline::Polyline, Config, Shape};
+
+/// Replaces the points with points spaced evenly along the path, at most `spacing` apart, see
+/// [`PathMeasure::resample`](crate::PathMeasure::resample).
+///
+/// Useful for making the output of [`Parametric`](super::Parametric) uniform in arc length.
+#[derive(Clone, Debug)]
+pub struct Resample<T> {
+    pub spacing: f32,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> Resample<T> {
+    #[inline]
+    pub const fn new(spacing: f32) -> Self {
+        Self {
+            spacing,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn check(&self) -> Result<(), Error> {
+        if !self.spacing.is_finite() {
+            Err(Error::NonFinite)
+        } else if self.spacing <= 0.0 {
+            Err(Error::Degenerate("spacing must be positive"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Shape for Resample<Polyline> {
+    type Input = Polyline;
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.check()?;
+        crate::error::finite(&input.points)?;
+
+        Ok(Polyline {
+            points: input.measure().resample(self.spacing),
+            direction: input.direction,
+        })
+    }
+}
+
+impl Shape for Resample<Polygon> {
+    type Input = Polygon;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.check()?;
+        crate::error::finite(&input.points)?;
+
+        // points on the boundary of a convex polygon form a convex polygon of the same winding
+        let is_convex = input.is_convex == Some(true);
+
+        Ok(Polygon {
+            points: input.measure().resample(self.spacing),
+            is_ccw: if is_convex { input.is_ccw } else { None },
+            is_convex: if is_convex { Some(true) } else { None },
+            is_simple: input.is_simple && is_convex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec2;
+
+    use super::*;
+
+    #[test]
+    fn spacing() {
+        let cfg = Config::default();
+        let line = Polyline::from(vec![Vec2::ZERO, Vec2::new(1.0, 0.0)]);
+
+        let points = Resample::<Polyline>::new(0.3).generate(&cfg, line.clone());
+        assert_eq!(points.points.len(), 5);
+        assert_eq!(points.points[1], Vec2::new(0.25, 0.0));
+
+        assert!(Resample::<Polyline>::new(0.0)
+            .try_generate(&cfg, line.clone())
+            .is_err());
+
+        // too small to be reached, but doesn't run out of memory
+        let points = Resample::<Polyline>::new(1e-30).generate(&cfg, line);
+        assert!(points.points.len() > 1 << 19);
+    }
+
+    #[test]
+    fn convex() {
+        let cfg = Config::default();
+        let triangle = Polygon::from(vec![Vec2::ZERO, Vec2::X, Vec2::Y]);
+        let triangle = Polygon {
+            is_ccw: Some(true),
+            is_convex: Some(true),
+            is_simple: true,
+            ..triangle
+        };
+
+        let polygon = Resample::<Polygon>::new(0.1).generate(&cfg, triangle);
+
+        assert_eq!(polygon.is_convex, Some(true));
+        assert_eq!(polygon.is_ccw, Some(true));
+        assert!(polygon.is_simple);
+        // the corners are cut off
+        let area = polygon.area();
+        assert!(area < 0.5 && area > 0.49, "{}", area);
+    }
+}
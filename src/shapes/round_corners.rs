@@ -0,0 +1,213 @@
+use glam::{Mat2, Vec2};
+
+use crate::{
+    error::Error, holed_polygon::HoledPolygon, polygon::Polygon, polyline::Polyline, Config, Shape,
+};
+
+/// Replaces every corner of `points`, the end points of open lines are kept.
+///
+/// `cut` is given the angle the corner turns by and returns how far along both edges the
+/// corner should be cut off, which is clamped such that adjacent cuts never overlap. `corner`
+/// then pushes the replacement given the corner point, the directions of both edges and the
+/// clamped cut.
+pub(super) fn replace_corners(
+    points: &[Vec2],
+    closed: bool,
+    cut: impl Fn(f32) -> f32,
+    mut corner: impl FnMut(&mut Vec<Vec2>, Vec2, Vec2, Vec2, f32),
+) -> Vec<Vec2> {
+    let len = points.len();
+
+    if len < 3 {
+        return points.to_vec();
+    }
+
+    let mut result = Vec::with_capacity(len);
+
+    let corners = if closed { 0..len } else { 1..len - 1 };
+
+    if !closed {
+        result.push(points[0]);
+    }
+
+    for i in corners {
+        let p0 = points[(i + len - 1) % len];
+        let p1 = points[i];
+        let p2 = points[(i + 1) % len];
+
+        let (e0, e1) = (p1 - p0, p2 - p1);
+        let (l0, l1) = (e0.length(), e1.length());
+
+        if l0 == 0.0 || l1 == 0.0 {
+            result.push(p1);
+            continue;
+        }
+
+        let (d0, d1) = (e0 / l0, e1 / l1);
+        let angle = d0.perp_dot(d1).atan2(d0.dot(d1));
+
+        if angle == 0.0 {
+            result.push(p1);
+            continue;
+        }
+
+        // edges are shared with the adjacent corner, except at the ends of lines
+        let a0 = if !closed && i == 1 { l0 } else { l0 / 2.0 };
+        let a1 = if !closed && i == len - 2 {
+            l1
+        } else {
+            l1 / 2.0
+        };
+
+        let t = cut(angle).min(a0).min(a1);
+
+        if t > 0.0 {
+            corner(&mut result, p1, d0, d1, t);
+        } else {
+            result.push(p1);
+        }
+    }
+
+    if !closed {
+        result.push(points[len - 1]);
+    }
+
+    // cuts meeting halfway along an edge produce near duplicates
+    let epsilon = |p: Vec2| p.abs().max_element().max(1.0) * f32::EPSILON * 4.0;
+
+    result.dedup_by(|b, a| a.distance(*b) <= epsilon(*a));
+
+    if closed
+        && result.len() > 1
+        && result[0].distance(result[result.len() - 1]) <= epsilon(result[0])
+    {
+        result.pop();
+    }
+
+    result
+}
+
+/// Rounds every corner of `points` with an arc of at most `radius`.
+fn round(cfg: &Config, points: &[Vec2], closed: bool, radius: f32) -> Vec<Vec2> {
+    replace_corners(
+        points,
+        closed,
+        |angle| radius * (angle.abs() / 2.0).tan(),
+        |result, p, d0, d1, t| {
+            let angle = d0.perp_dot(d1).atan2(d0.dot(d1));
+
+            // the radius shrinks if the cut was clamped
+            let radius = t / (angle.abs() / 2.0).tan();
+
+            let a = p - d0 * t;
+            let center = a + d0.perp() * angle.signum() * radius;
+
+            let steps = cfg.arc_steps(radius, angle);
+
+            result.push(a);
+
+            for step in 1..steps {
+                let rotation = Mat2::from_angle(angle * step as f32 / steps as f32);
+
+                result.push(center + rotation * (a - center));
+            }
+
+            result.push(p + d1 * t);
+        },
+    )
+}
+
+/// Rounds the corners of a shape with arcs, the radius is reduced where edges are too short
+/// to fit it.
+#[derive(Clone, Debug)]
+pub struct RoundCorners<T> {
+    pub radius: f32,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> RoundCorners<T> {
+    #[inline]
+    pub const fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl Shape for RoundCorners<Polyline> {
+    type Input = Polyline;
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&input.points)?;
+
+        Ok(Polyline {
+            points: round(cfg, &input.points, false, self.radius),
+            direction: input.direction,
+        })
+    }
+}
+
+impl Shape for RoundCorners<Polygon> {
+    type Input = Polygon;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&input.points)?;
+
+        Ok(corners(input, |points| round(cfg, points, true, self.radius)))
+    }
+}
+
+impl Shape for RoundCorners<HoledPolygon> {
+    type Input = HoledPolygon;
+    type Output = HoledPolygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        let ring = |polygon: Polygon| {
+            crate::error::finite(&polygon.points)?;
+
+            Ok(corners(polygon, |points| round(cfg, points, true, self.radius)))
+        };
+
+        Ok(HoledPolygon {
+            polygon: ring(input.polygon)?,
+            holes: input.holes.into_iter().map(ring).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Replaces the corners of `polygon` with `f`, keeping the guarantees that still hold.
+///
+/// Cutting corners keeps convex polygons convex, but concave corners may grow into other
+/// parts of the polygon.
+#[inline]
+pub(super) fn corners(polygon: Polygon, f: impl FnOnce(&[Vec2]) -> Vec<Vec2>) -> Polygon {
+    let is_convex = polygon.is_convex == Some(true);
+
+    Polygon {
+        points: f(&polygon.points),
+        is_ccw: polygon.is_ccw,
+        is_convex: if is_convex { Some(true) } else { None },
+        is_simple: polygon.is_simple && is_convex,
+    }
+}
@@ -0,0 +1,104 @@
+use std::f32::consts::FRAC_PI_2;
+
+use glam::Vec2;
+
+use crate::{error::Error, polygon::Polygon, Config, Shape};
+
+/// Creates a rectangle centered at the origin with rounded corners.
+///
+/// The resulting polygon will always be:
+/// * Simple
+/// * Convex
+/// * CCW
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundedRect {
+    pub width: f32,
+    pub height: f32,
+    /// Radius of the bottom left, bottom right, top right and top left corners, each clamped to
+    /// half the width and height.
+    pub radii: [f32; 4],
+}
+
+impl RoundedRect {
+    #[inline]
+    pub fn new(width: f32, height: f32, radius: f32) -> Self {
+        Self {
+            width,
+            height,
+            radii: [radius; 4],
+        }
+    }
+
+    /// Sets the radius of each corner, starting at the bottom left going counter clockwise.
+    #[inline]
+    pub fn radii(mut self, radii: [f32; 4]) -> Self {
+        self.radii = radii;
+        self
+    }
+}
+
+impl Shape for RoundedRect {
+    type Input = ();
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, _: Self::Input) -> Self::Output {
+        let half = Vec2::new(self.width, self.height) / 2.0;
+        let max_radius = half.min_element();
+
+        let corners = [
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(1.0, -1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(-1.0, 1.0),
+        ];
+
+        let mut polygon = Polygon::default();
+
+        for (i, (corner, radius)) in corners.iter().zip(self.radii.iter()).enumerate() {
+            let radius = radius.clamp(0.0, max_radius);
+            let center = *corner * (half - Vec2::splat(radius));
+
+            // the bottom left corner starts pointing left
+            let start = FRAC_PI_2 * (i as f32 + 2.0);
+            let steps = cfg.arc_steps(radius, FRAC_PI_2);
+
+            if steps == 0 {
+                polygon.push(center);
+                continue;
+            }
+
+            for step in 0..=steps {
+                let a = start + FRAC_PI_2 * step as f32 / steps as f32;
+
+                polygon.push(center + Vec2::new(a.cos(), a.sin()) * radius);
+            }
+        }
+
+        // corners with a radius of half the side share their end points
+        polygon.points.dedup();
+
+        if polygon.points.len() > 1 && polygon.points.first() == polygon.points.last() {
+            polygon.points.pop();
+        }
+
+        polygon.is_ccw = Some(true);
+        polygon.is_convex = Some(true);
+        polygon.is_simple = true;
+
+        polygon
+    }
+
+    /// Returns an error if the width or height isn't positive or a radius isn't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::positive(self.width, "width must be positive")?;
+        crate::error::positive(self.height, "height must be positive")?;
+
+        if !self.radii.iter().all(|radius| radius.is_finite()) {
+            return Err(Error::NonFinite);
+        }
+
+        Ok(self.generate(cfg, input))
+    }
+}
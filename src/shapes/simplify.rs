@@ -0,0 +1,147 @@
+use crate::{
+    error::Error,
+    holed_polygon::HoledPolygon,
+    polygon::Polygon,
+    polyline::Polyline,
+    simplify::{self, Chain},
+    Config, Shape,
+};
+
+/// Algorithm used by [`Simplify`] to remove points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Douglas–Peucker, keeps the result within `tolerance` distance of the original, well
+    /// suited for removing excess points along curves.
+    DouglasPeucker,
+    /// Visvalingam–Whyatt, removes points until every point forms a triangle with an area of at
+    /// least `tolerance` with its neighbours, which tends to keep the overall shape better when
+    /// simplifying aggressively.
+    VisvalingamWhyatt,
+}
+
+impl Default for Algorithm {
+    #[inline]
+    fn default() -> Self {
+        Self::DouglasPeucker
+    }
+}
+
+/// Reduces the number of points, without introducing intersections.
+///
+/// Polygons are verified beforehand, the result is thus always simple and can be filled
+/// directly.
+#[derive(Clone, Debug)]
+pub struct Simplify<T> {
+    pub tolerance: f32,
+    pub algorithm: Algorithm,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> Simplify<T> {
+    #[inline]
+    pub const fn new(tolerance: f32, algorithm: Algorithm) -> Self {
+        Self {
+            tolerance,
+            algorithm,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn simplify(&self, chains: &[Chain]) -> Vec<Vec<glam::Vec2>> {
+        match self.algorithm {
+            Algorithm::DouglasPeucker => simplify::douglas_peucker(chains, self.tolerance),
+            Algorithm::VisvalingamWhyatt => simplify::visvalingam_whyatt(chains, self.tolerance),
+        }
+    }
+}
+
+impl Shape for Simplify<Polyline> {
+    type Input = Polyline;
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&input.points)?;
+
+        let chain = Chain {
+            points: &input.points,
+            closed: false,
+        };
+
+        Ok(Polyline {
+            points: self.simplify(&[chain]).remove(0),
+            direction: input.direction,
+        })
+    }
+}
+
+impl Shape for Simplify<Polygon> {
+    type Input = Polygon;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, mut input: Self::Input) -> Result<Self::Output, Error> {
+        input.try_verify()?;
+
+        let chain = Chain {
+            points: &input.points,
+            closed: true,
+        };
+
+        // removing points of a convex polygon never makes it concave
+        Ok(Polygon {
+            points: self.simplify(&[chain]).remove(0),
+            ..input
+        })
+    }
+}
+
+impl Shape for Simplify<HoledPolygon> {
+    type Input = HoledPolygon;
+    type Output = HoledPolygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, mut input: Self::Input) -> Result<Self::Output, Error> {
+        input.try_verify()?;
+
+        let chains: Vec<_> = std::iter::once(&input.polygon)
+            .chain(&input.holes)
+            .map(|polygon| Chain {
+                points: &polygon.points,
+                closed: true,
+            })
+            .collect();
+
+        let mut rings = self.simplify(&chains).into_iter();
+
+        let polygon = Polygon {
+            points: rings.next().unwrap(),
+            ..input.polygon
+        };
+
+        let holes = input
+            .holes
+            .into_iter()
+            .zip(rings)
+            .map(|(hole, points)| Polygon { points, ..hole })
+            .collect();
+
+        Ok(HoledPolygon { polygon, holes })
+    }
+}
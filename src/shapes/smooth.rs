@@ -0,0 +1,140 @@
+use glam::Vec2;
+
+use crate::{
+    error::Error, holed_polygon::HoledPolygon, polygon::Polygon, polyline::Polyline, Config, Shape,
+};
+
+/// Cuts every corner of `points` at a quarter and three quarters of each edge, `iterations`
+/// times, the end points of open lines are kept.
+fn chaikin(points: &[Vec2], closed: bool, iterations: usize) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+
+    for _ in 0..iterations {
+        if points.len() < 3 {
+            break;
+        }
+
+        let len = points.len();
+        let edges = if closed { len } else { len - 1 };
+
+        let mut smoothed = Vec::with_capacity(edges * 2 + 2);
+
+        if !closed {
+            smoothed.push(points[0]);
+        }
+
+        for i in 0..edges {
+            let p0 = points[i];
+            let p1 = points[(i + 1) % len];
+
+            // the ends of lines aren't cut, keeping the first and last edge attached
+            if closed || i > 0 {
+                smoothed.push(p0.lerp(p1, 0.25));
+            }
+
+            if closed || i < edges - 1 {
+                smoothed.push(p0.lerp(p1, 0.75));
+            }
+        }
+
+        if !closed {
+            smoothed.push(points[len - 1]);
+        }
+
+        points = smoothed;
+    }
+
+    points
+}
+
+/// Smooths a shape with Chaikin's corner cutting algorithm, which approaches a quadratic
+/// B-spline as `iterations` increases.
+///
+/// Every iteration doubles the number of points.
+#[derive(Clone, Debug)]
+pub struct Smooth<T> {
+    pub iterations: usize,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> Smooth<T> {
+    #[inline]
+    pub const fn new(iterations: usize) -> Self {
+        Self {
+            iterations,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn polygon(&self, polygon: Polygon) -> Result<Polygon, Error> {
+        crate::error::finite(&polygon.points)?;
+
+        // cutting corners keeps convex polygons convex
+        let is_convex = polygon.is_convex == Some(true);
+
+        Ok(Polygon {
+            points: chaikin(&polygon.points, true, self.iterations),
+            is_ccw: polygon.is_ccw,
+            is_convex: if is_convex { Some(true) } else { None },
+            is_simple: polygon.is_simple && is_convex,
+        })
+    }
+}
+
+impl Shape for Smooth<Polyline> {
+    type Input = Polyline;
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&input.points)?;
+
+        Ok(Polyline {
+            points: chaikin(&input.points, false, self.iterations),
+            direction: input.direction,
+        })
+    }
+}
+
+impl Shape for Smooth<Polygon> {
+    type Input = Polygon;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.polygon(input)
+    }
+}
+
+impl Shape for Smooth<HoledPolygon> {
+    type Input = HoledPolygon;
+    type Output = HoledPolygon;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        Ok(HoledPolygon {
+            polygon: self.polygon(input.polygon)?,
+            holes: input
+                .holes
+                .into_iter()
+                .map(|hole| self.polygon(hole))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{polygon::Polygon, Shape};
+use crate::{error::Error, polygon::Polygon, Shape};
 use std::any::Any;
 
 #[derive(Clone, Debug)]
@@ -27,4 +27,21 @@ where
             self.u.generate(cfg, input),
         )
     }
+
+    #[inline]
+    fn try_generate(
+        &self,
+        cfg: &crate::Config,
+        mut input: Self::Input,
+    ) -> Result<Self::Output, Error> {
+        if let Some(input) = <dyn Any>::downcast_mut::<Polygon>(&mut input) {
+            crate::error::finite(&input.points)?;
+            input.verify();
+        }
+
+        Ok((
+            self.t.try_generate(cfg, input.clone())?,
+            self.u.try_generate(cfg, input)?,
+        ))
+    }
 }
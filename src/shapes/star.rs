@@ -0,0 +1,85 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use glam::Vec2;
+
+use crate::{error::Error, polygon::Polygon, Config, Shape};
+
+/// Creates a star with `points` tips at `outer_radius` and the corners between them at
+/// `inner_radius`, with the first tip pointing up.
+///
+/// Fewer than two `points` result in an empty polygon, otherwise the resulting polygon will
+/// always be:
+/// * Simple
+/// * CCW
+/// * Convex if no corner points inwards, when
+///   `outer_radius * cos(PI / points) <= inner_radius <= outer_radius / cos(PI / points)`,
+///   which always holds for two points
+#[derive(Clone, Debug, PartialEq)]
+pub struct Star {
+    pub points: usize,
+    pub outer_radius: f32,
+    pub inner_radius: f32,
+}
+
+impl Star {
+    #[inline]
+    pub fn new(points: usize, outer_radius: f32, inner_radius: f32) -> Self {
+        Self {
+            points,
+            outer_radius,
+            inner_radius,
+        }
+    }
+}
+
+impl Shape for Star {
+    type Input = ();
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, _cfg: &Config, _: Self::Input) -> Self::Output {
+        if self.points < 2 {
+            return Polygon::default();
+        }
+
+        let step = PI / self.points as f32;
+
+        let mut polygon = Polygon::with_capacity(self.points * 2);
+
+        for i in 0..self.points * 2 {
+            let a = FRAC_PI_2 + i as f32 * step;
+            let radius = if i % 2 == 0 {
+                self.outer_radius
+            } else {
+                self.inner_radius
+            };
+
+            polygon.push(Vec2::new(a.cos(), a.sin()) * radius);
+        }
+
+        polygon.is_ccw = Some(true);
+        // inner corners pointing inwards or tips pointing inwards when the inner radius is larger,
+        // two tips always form a rhombus
+        let (inner, outer) = (self.inner_radius, self.outer_radius);
+        let cos = step.cos();
+        polygon.is_convex =
+            Some(self.points == 2 || (outer * cos <= inner && inner <= outer / cos));
+        polygon.is_simple = true;
+
+        polygon
+    }
+
+    /// Returns an error instead of an empty polygon for fewer than two points, or if a radius
+    /// isn't positive.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        if self.points < 2 {
+            return Err(Error::Degenerate("star must have at least two points"));
+        }
+
+        crate::error::positive(self.outer_radius, "radii must be positive")?;
+        crate::error::positive(self.inner_radius, "radii must be positive")?;
+
+        Ok(self.generate(cfg, input))
+    }
+}
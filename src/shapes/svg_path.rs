@@ -0,0 +1,619 @@
+use std::{f32::consts::TAU, fmt, iter::Peekable, str::CharIndices};
+
+use glam::{Mat2, Vec2};
+
+use crate::{
+    boolean, error::Error, holed_polygon::HoledPolygon, polyline::Polyline, Config, Shape,
+};
+
+use super::FillRule;
+
+/// Error produced when parsing svg path data.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SvgPathError {
+    /// Unexpected character at byte offset.
+    UnexpectedChar(usize, char),
+    /// Path data ended in the middle of a command.
+    UnexpectedEnd,
+    /// Path data didn't start with a moveto command.
+    MissingMoveTo,
+}
+
+impl fmt::Display for SvgPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedChar(i, c) => write!(f, "unexpected character '{}' at {}", c, i),
+            Self::UnexpectedEnd => write!(f, "unexpected end of path data"),
+            Self::MissingMoveTo => write!(f, "path data must start with a moveto"),
+        }
+    }
+}
+
+impl std::error::Error for SvgPathError {}
+
+/// Path segment with absolute coordinates.
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Line(Vec2),
+    Quad(Vec2, Vec2),
+    Cubic(Vec2, Vec2, Vec2),
+    Arc {
+        radii: Vec2,
+        rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        end: Vec2,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Subpath {
+    start: Vec2,
+    segments: Vec<Segment>,
+    closed: bool,
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    #[inline]
+    fn skip_separators(&mut self) {
+        while let Some((_, c)) = self.chars.peek() {
+            if c.is_whitespace() || *c == ',' {
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Checks if the next token is a number, without consuming it.
+    #[inline]
+    fn at_number(&mut self) -> bool {
+        self.skip_separators();
+
+        matches!(self.chars.peek(), Some((_, c)) if c.is_ascii_digit() || "+-.".contains(*c))
+    }
+
+    fn number(&mut self) -> Result<f32, SvgPathError> {
+        self.skip_separators();
+
+        let mut number = String::new();
+
+        if let Some((_, c)) = self.chars.peek() {
+            if *c == '+' || *c == '-' {
+                number.push(*c);
+                self.chars.next();
+            }
+        }
+
+        let mut dot = false;
+        let mut exponent = false;
+
+        while let Some((_, c)) = self.chars.peek().copied() {
+            if c.is_ascii_digit() {
+                number.push(c);
+            } else if c == '.' && !dot && !exponent {
+                dot = true;
+                number.push(c);
+            } else if (c == 'e' || c == 'E') && !exponent && !number.is_empty() {
+                exponent = true;
+                number.push(c);
+                self.chars.next();
+
+                if let Some((_, c)) = self.chars.peek().copied() {
+                    if c == '+' || c == '-' {
+                        number.push(c);
+                        self.chars.next();
+                    }
+                }
+
+                continue;
+            } else {
+                break;
+            }
+
+            self.chars.next();
+        }
+
+        number.parse().map_err(|_| match self.chars.peek() {
+            Some((i, c)) => SvgPathError::UnexpectedChar(*i, *c),
+            None => SvgPathError::UnexpectedEnd,
+        })
+    }
+
+    #[inline]
+    fn point(&mut self) -> Result<Vec2, SvgPathError> {
+        Ok(Vec2::new(self.number()?, self.number()?))
+    }
+
+    /// Flags may be written without separators, eg. `a1 1 0 00 1 1`.
+    #[inline]
+    fn flag(&mut self) -> Result<bool, SvgPathError> {
+        self.skip_separators();
+
+        match self.chars.next() {
+            Some((_, '0')) => Ok(false),
+            Some((_, '1')) => Ok(true),
+            Some((i, c)) => Err(SvgPathError::UnexpectedChar(i, c)),
+            None => Err(SvgPathError::UnexpectedEnd),
+        }
+    }
+}
+
+fn parse(d: &str) -> Result<Vec<Subpath>, SvgPathError> {
+    let mut parser = Parser {
+        chars: d.char_indices().peekable(),
+    };
+
+    let mut subpaths: Vec<Subpath> = Vec::new();
+
+    let mut current = Vec2::ZERO;
+    let mut start = Vec2::ZERO;
+    // reflected control points for smooth curves
+    let mut last_cubic: Option<Vec2> = None;
+    let mut last_quad: Option<Vec2> = None;
+
+    let mut command: Option<char> = None;
+
+    loop {
+        parser.skip_separators();
+
+        let (index, c) = match parser.chars.peek().copied() {
+            Some((i, c)) if c.is_ascii_alphabetic() => {
+                parser.chars.next();
+                (i, c)
+            }
+            Some((i, c)) => match command {
+                // repeated command, a moveto is followed by implicit linetos
+                Some('M') if parser.at_number() => (i, 'L'),
+                Some('m') if parser.at_number() => (i, 'l'),
+                // a closepath takes no arguments, repeating it would never consume input
+                Some('Z' | 'z') => return Err(SvgPathError::UnexpectedChar(i, c)),
+                Some(command) if parser.at_number() => (i, command),
+                _ => return Err(SvgPathError::UnexpectedChar(i, c)),
+            },
+            None => break,
+        };
+
+        let relative = c.is_ascii_lowercase();
+        let offset = if relative { current } else { Vec2::ZERO };
+
+        if subpaths.is_empty() && !c.eq_ignore_ascii_case(&'M') {
+            return Err(SvgPathError::MissingMoveTo);
+        }
+
+        // any drawing command after a closepath starts a new subpath at the same point
+        if !matches!(c, 'M' | 'm' | 'Z' | 'z') && matches!(subpaths.last(), Some(s) if s.closed) {
+            subpaths.push(Subpath {
+                start,
+                segments: Vec::new(),
+                closed: false,
+            });
+        }
+
+        let mut cubic = None;
+        let mut quad = None;
+
+        match c.to_ascii_uppercase() {
+            'M' => {
+                current = parser.point()? + offset;
+                start = current;
+
+                subpaths.push(Subpath {
+                    start,
+                    segments: Vec::new(),
+                    closed: false,
+                });
+            }
+            'L' => current = parser.point()? + offset,
+            'H' => current.x = parser.number()? + offset.x,
+            'V' => current.y = parser.number()? + offset.y,
+            'C' => {
+                let c1 = parser.point()? + offset;
+                let c2 = parser.point()? + offset;
+                let end = parser.point()? + offset;
+
+                subpaths
+                    .last_mut()
+                    .unwrap()
+                    .segments
+                    .push(Segment::Cubic(c1, c2, end));
+
+                cubic = Some(c2);
+                current = end;
+            }
+            'S' => {
+                let c1 = last_cubic.map_or(current, |c| current * 2.0 - c);
+                let c2 = parser.point()? + offset;
+                let end = parser.point()? + offset;
+
+                subpaths
+                    .last_mut()
+                    .unwrap()
+                    .segments
+                    .push(Segment::Cubic(c1, c2, end));
+
+                cubic = Some(c2);
+                current = end;
+            }
+            'Q' => {
+                let ctrl = parser.point()? + offset;
+                let end = parser.point()? + offset;
+
+                subpaths
+                    .last_mut()
+                    .unwrap()
+                    .segments
+                    .push(Segment::Quad(ctrl, end));
+
+                quad = Some(ctrl);
+                current = end;
+            }
+            'T' => {
+                let ctrl = last_quad.map_or(current, |c| current * 2.0 - c);
+                let end = parser.point()? + offset;
+
+                subpaths
+                    .last_mut()
+                    .unwrap()
+                    .segments
+                    .push(Segment::Quad(ctrl, end));
+
+                quad = Some(ctrl);
+                current = end;
+            }
+            'A' => {
+                let radii = parser.point()?;
+                let rotation = parser.number()?.to_radians();
+                let large_arc = parser.flag()?;
+                let sweep = parser.flag()?;
+                let end = parser.point()? + offset;
+
+                subpaths.last_mut().unwrap().segments.push(Segment::Arc {
+                    radii,
+                    rotation,
+                    large_arc,
+                    sweep,
+                    end,
+                });
+
+                current = end;
+            }
+            'Z' => {
+                subpaths.last_mut().unwrap().closed = true;
+                current = start;
+            }
+            _ => return Err(SvgPathError::UnexpectedChar(index, c)),
+        }
+
+        if matches!(c, 'L' | 'l' | 'H' | 'h' | 'V' | 'v') {
+            subpaths
+                .last_mut()
+                .unwrap()
+                .segments
+                .push(Segment::Line(current));
+        }
+
+        last_cubic = cubic;
+        last_quad = quad;
+        command = Some(c);
+    }
+
+    Ok(subpaths)
+}
+
+/// Appends an svg elliptical arc, see the
+/// [implementation notes](https://www.w3.org/TR/SVG11/implnote.html#ArcConversionEndpointToCenter)
+/// for the conversion to center parameterization.
+fn arc_to(
+    polyline: &mut Polyline,
+    cfg: &Config,
+    radii: Vec2,
+    rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    end: Vec2,
+) {
+    let p0 = polyline.points[polyline.points.len() - 1];
+
+    let mut radii = radii.abs();
+
+    if radii.x == 0.0 || radii.y == 0.0 || p0 == end {
+        polyline.push(end);
+        return;
+    }
+
+    let rot = Mat2::from_angle(rotation);
+    let p = Mat2::from_angle(-rotation) * ((p0 - end) / 2.0);
+
+    // scale radii up if they can't span the endpoints
+    let lambda = (p / radii).length_squared();
+
+    if lambda > 1.0 {
+        radii *= lambda.sqrt();
+    }
+
+    let (rx2, ry2) = (radii.x * radii.x, radii.y * radii.y);
+    let (px2, py2) = (p.x * p.x, p.y * p.y);
+
+    let mut coef = ((rx2 * ry2 - rx2 * py2 - ry2 * px2) / (rx2 * py2 + ry2 * px2))
+        .max(0.0)
+        .sqrt();
+
+    if large_arc == sweep {
+        coef = -coef;
+    }
+
+    let c = Vec2::new(radii.x * p.y / radii.y, -radii.y * p.x / radii.x) * coef;
+    let center = rot * c + (p0 + end) / 2.0;
+
+    let u = (p - c) / radii;
+    let v = (-p - c) / radii;
+
+    let start_angle = u.y.atan2(u.x);
+    let mut delta = u.perp_dot(v).atan2(u.dot(v));
+
+    if !sweep && delta > 0.0 {
+        delta -= TAU;
+    } else if sweep && delta < 0.0 {
+        delta += TAU;
+    }
+
+    let steps = cfg.arc_steps(radii.max_element(), delta).max(1);
+
+    for step in 1..steps {
+        let a = start_angle + delta * step as f32 / steps as f32;
+
+        polyline.push(center + rot * (Vec2::new(a.cos(), a.sin()) * radii));
+    }
+
+    polyline.push(end);
+}
+
+/// Flattens all subpaths, closed subpaths are returned as closed.
+fn flatten(subpaths: &[Subpath], cfg: &Config) -> Vec<(Polyline, bool)> {
+    subpaths
+        .iter()
+        .map(|subpath| {
+            let mut polyline = Polyline::from(vec![subpath.start]);
+
+            for segment in &subpath.segments {
+                match *segment {
+                    Segment::Line(p) => polyline.push(p),
+                    Segment::Quad(ctrl, end) => polyline.quad_to(ctrl, end, cfg.flatness()),
+                    Segment::Cubic(c1, c2, end) => polyline.cubic_to(c1, c2, end, cfg.flatness()),
+                    Segment::Arc {
+                        radii,
+                        rotation,
+                        large_arc,
+                        sweep,
+                        end,
+                    } => arc_to(&mut polyline, cfg, radii, rotation, large_arc, sweep, end),
+                }
+            }
+
+            (polyline, subpath.closed)
+        })
+        .collect()
+}
+
+/// Shape generated from svg path data, eg. the `d` attribute of a `<path>`.
+///
+/// Use [`SvgPath::lines`] to get every subpath as a [`Polyline`], closed subpaths end at their
+/// starting point. Use [`SvgPath::polygons`] to fill the path, each subpath is closed and the
+/// regions inside are found by [`FillRule`], the svg default being [`FillRule::NonZero`], such
+/// that a subpath inside another is a hole if it winds the opposite way.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgPath<T, R = ()> {
+    subpaths: Vec<Subpath>,
+    /// Which regions of polygons are filled, see [`SvgPath::fill_rule`], lines have none.
+    fill_rule: R,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl SvgPath<Vec<Polyline>> {
+    /// Parses path data generating [`Polyline`]s.
+    #[inline]
+    pub fn lines(d: &str) -> Result<Self, SvgPathError> {
+        Ok(Self {
+            subpaths: parse(d)?,
+            fill_rule: (),
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+impl SvgPath<Vec<HoledPolygon>, FillRule> {
+    /// Parses path data generating [`HoledPolygon`]s.
+    #[inline]
+    pub fn polygons(d: &str) -> Result<Self, SvgPathError> {
+        Ok(Self {
+            subpaths: parse(d)?,
+            fill_rule: FillRule::NonZero,
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    /// Sets the [`FillRule`], matching the svg `fill-rule` property.
+    #[inline]
+    pub fn fill_rule(mut self, rule: FillRule) -> Self {
+        self.fill_rule = rule;
+        self
+    }
+}
+
+impl Shape for SvgPath<Vec<Polyline>> {
+    type Input = ();
+    type Output = Vec<Polyline>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, _: Self::Input) -> Self::Output {
+        flatten(&self.subpaths, cfg)
+            .into_iter()
+            .filter_map(|(mut polyline, closed)| {
+                if closed && polyline.points.first() != polyline.points.last() {
+                    polyline.push(polyline.points[0]);
+                }
+
+                if polyline.points.len() >= 2 {
+                    Some(polyline)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+impl Shape for SvgPath<Vec<HoledPolygon>, FillRule> {
+    type Input = ();
+    type Output = Vec<HoledPolygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_generate(&self, cfg: &Config, _: Self::Input) -> Result<Self::Output, Error> {
+        let rings: Vec<Vec<Vec2>> = flatten(&self.subpaths, cfg)
+            .into_iter()
+            .map(|(polyline, _)| polyline.points)
+            .filter(|points| points.len() >= 3)
+            .collect();
+
+        for ring in &rings {
+            crate::error::finite(ring)?;
+        }
+
+        let rings: Vec<&[Vec2]> = rings.iter().map(Vec::as_slice).collect();
+        let rule = self.fill_rule;
+
+        // every subpath adds to the same winding number, like svg does
+        Ok(boolean::resolve(&[rings], |windings| {
+            rule.is_inside(windings[0])
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(d: &str) -> Vec<Vec<Vec2>> {
+        SvgPath::lines(d)
+            .unwrap()
+            .generate(&Config::default(), ())
+            .into_iter()
+            .map(|line| line.points)
+            .collect()
+    }
+
+    #[test]
+    fn number_after_closepath() {
+        let d = "M0 0 L1 0 L1 1 Z 5";
+
+        assert_eq!(
+            parse(d),
+            Err(SvgPathError::UnexpectedChar(d.len() - 1, '5'))
+        );
+    }
+
+    #[test]
+    fn missing_moveto() {
+        assert_eq!(parse("L1 1"), Err(SvgPathError::MissingMoveTo));
+        assert_eq!(parse("M0 0 L1"), Err(SvgPathError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn implicit_lineto() {
+        let expected = vec![vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+        ]];
+
+        assert_eq!(lines("M0 0 1 0 1 1"), expected);
+        assert_eq!(lines("M0,0L1,0,1,1"), expected);
+        assert_eq!(lines("m0 0 1 0 0 1"), expected);
+    }
+
+    #[test]
+    fn relative() {
+        let expected = vec![vec![
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(1.0, 1.0),
+        ]];
+
+        assert_eq!(lines("m1 1 l1 0 v1 h-1 z"), expected);
+        assert_eq!(lines("M1 1 L2 1 V2 H1 Z"), expected);
+
+        // a drawing command after a closepath starts at the start of the closed subpath
+        assert_eq!(
+            lines("m1 1 h1 v1 z l-1 0"),
+            vec![
+                expected[0][..3]
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once(Vec2::new(1.0, 1.0)))
+                    .collect(),
+                vec![Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn arcs() {
+        // flags may be written without separators
+        assert_eq!(parse("M0 0 A1 1 0 0 1 2 0"), parse("M0 0a1 1 0 012 0"));
+
+        let arc = &lines("M0 0 A1 1 0 0 1 2 0")[0];
+
+        assert!(arc.len() > 3);
+        assert_eq!(arc[arc.len() - 1], Vec2::new(2.0, 0.0));
+
+        // half circle around (1, 0), sweeping through negative y, which is up in svg
+        for p in arc {
+            assert!((p.distance(Vec2::new(1.0, 0.0)) - 1.0).abs() < 1e-4);
+            assert!(p.y <= 1e-4);
+        }
+
+        // radii too small to span the endpoints are scaled up
+        let arc = &lines("M0 0 A0.1 0.1 0 0 1 2 0")[0];
+
+        for p in arc {
+            assert!((p.distance(Vec2::new(1.0, 0.0)) - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn nested_subpaths() {
+        let cfg = Config::default();
+        let d = "M0 0 H10 V10 H0 Z M2 2 H8 V8 H2 Z";
+
+        let nonzero = SvgPath::polygons(d).unwrap().generate(&cfg, ());
+
+        assert_eq!(nonzero.len(), 1);
+        assert!(nonzero[0].holes.is_empty());
+
+        let even_odd = SvgPath::polygons(d)
+            .unwrap()
+            .fill_rule(FillRule::EvenOdd)
+            .generate(&cfg, ());
+
+        assert_eq!(even_odd.len(), 1);
+        assert_eq!(even_odd[0].holes.len(), 1);
+
+        // winding the opposite way is a hole either way
+        let d = "M0 0 H10 V10 H0 Z M2 2 V8 H8 V2 Z";
+
+        let opposite = SvgPath::polygons(d).unwrap().generate(&cfg, ());
+
+        assert_eq!(opposite[0].holes.len(), 1);
+    }
+}
@@ -1,122 +1,451 @@
-use std::f32::consts::PI;
-
 use glam::Vec2;
 
-use crate::{Config, Shape, ShapeExt, polygon::Polygon, polyline::Polyline};
+use crate::{error::Error, polygon::Polygon, polyline::Polyline, Config, Shape};
+
+/// How the outer side of corners in a [`Thicken`]ed line is joined.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineJoin {
+    /// Extends the edges until they meet, falls back to [`LineJoin::Bevel`] when the ratio
+    /// between the miter length and the thickness exceeds `limit`.
+    Miter { limit: f32 },
+    /// Cuts the corner off with a straight line.
+    Bevel,
+    /// Rounds the corner with an arc.
+    Round,
+}
+
+impl Default for LineJoin {
+    /// Miter with a limit of 4, the same as svg.
+    #[inline]
+    fn default() -> Self {
+        Self::Miter { limit: 4.0 }
+    }
+}
+
+/// Shape of the ends of a [`Thicken`]ed line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineCap {
+    /// Ends flat at the end point.
+    Butt,
+    /// Ends flat, extended by half the thickness.
+    Square,
+    /// Ends with a half circle.
+    Round,
+    /// Ends in a point `length` past the end point.
+    Triangle { length: f32 },
+    /// Ends in an arrowhead `width` wide at the end point, with the tip `length` past it.
+    Arrow { width: f32, length: f32 },
+}
+
+impl Default for LineCap {
+    #[inline]
+    fn default() -> Self {
+        Self::Butt
+    }
+}
 
+/// Thickens a [`Polyline`] to a constant width, or every line of a `Vec<Polyline>` such as
+/// [`Dash`](super::Dash)es.
+///
+/// Where the line overlaps itself or sharp inner corners fold over, the crossings of the
+/// outline are removed, see [`Thicken::overlaps`] to fill the overlapping parts instead.
+///
+/// A single point is thickened into just its caps, oriented by [`Polyline::direction`], so dots
+/// with round caps become circles.
 #[derive(Clone, Debug)]
-pub struct Thicken {
+pub struct Thicken<T> {
     pub thickness: f32,
-    pub round: bool,
+    pub join: LineJoin,
+    pub start_cap: LineCap,
+    pub end_cap: LineCap,
+    /// Keeps the crossings of the outline, see [`Thicken::overlaps`].
+    pub overlaps: bool,
+    _marker: std::marker::PhantomData<*const T>,
 }
 
-impl Shape for Thicken {
-    type Input = Polyline;
-    type Output = Polygon;
+impl<T> Thicken<T> {
+    #[inline]
+    pub fn new(thickness: f32) -> Self {
+        Self {
+            thickness,
+            join: LineJoin::default(),
+            start_cap: LineCap::default(),
+            end_cap: LineCap::default(),
+            overlaps: false,
+            _marker: std::marker::PhantomData,
+        }
+    }
 
+    /// Sets the [`LineCap`] of both ends.
     #[inline]
-    fn generate(&self, cfg: &Config, line: Self::Input) -> Self::Output {
-        #[inline]
-        fn push_cap(polygon: &mut Polygon, cfg: &Config, p: Vec2, thickness: f32, angle: f32) {
-            let dist = PI * thickness;
-            let steps = (dist / cfg.resolution).ceil() as usize;
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.start_cap = cap;
+        self.end_cap = cap;
+        self
+    }
 
-            for step in 0..=steps {
-                let l = step as f32 / steps as f32;
-                let a = angle + l * PI;
+    /// Sets the [`LineCap`] at the start of the line.
+    #[inline]
+    pub fn start_cap(mut self, cap: LineCap) -> Self {
+        self.start_cap = cap;
+        self
+    }
 
-                let p = p + Vec2::new(a.cos(), a.sin()) * thickness;
+    /// Sets the [`LineCap`] at the end of the line.
+    #[inline]
+    pub fn end_cap(mut self, cap: LineCap) -> Self {
+        self.end_cap = cap;
+        self
+    }
 
-                polygon.push(p);
-            }
-        }
+    /// Sets the [`LineJoin`].
+    #[inline]
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Keeps the outline crossing itself where the line overlaps itself, instead of removing
+    /// the crossings.
+    ///
+    /// Filling it with [`FillRule::NonZero`](super::FillRule::NonZero) fills every part the
+    /// line covers, see [`Fill::fill_rule`](super::Fill::fill_rule).
+    #[inline]
+    pub fn overlaps(mut self) -> Self {
+        self.overlaps = true;
+        self
+    }
+}
+
+#[inline]
+pub(super) fn right(v: Vec2) -> Vec2 {
+    Vec2::new(v.y, -v.x)
+}
+
+/// Pushes points on an arc around `p` from `r0` to `r1`, excluding the end points.
+#[inline]
+pub(super) fn push_arc(
+    polygon: &mut Polygon,
+    cfg: &Config,
+    p: Vec2,
+    r0: Vec2,
+    r1: Vec2,
+    radius: f32,
+) {
+    let angle = r0.perp_dot(r1).atan2(r0.dot(r1));
+    let start = r0.y.atan2(r0.x);
+
+    let steps = cfg.arc_steps(radius, angle);
+
+    for step in 1..steps {
+        let a = start + angle * step as f32 / steps as f32;
+
+        polygon.push(p + Vec2::new(a.cos(), a.sin()) * radius);
+    }
+}
 
-        #[inline]
-        fn nor(v: Vec2) -> Vec2 {
-            Vec2::new(-v.y, v.x)
+/// Pushes a cap at `p` from the right side at `p + r0 * h` to the left side at `p + r1 * h`,
+/// excluding the points on the sides.
+///
+/// `r0` and `r1` are unit normals, mirrored across the direction of the line at `p`.
+#[inline]
+pub(super) fn push_cap(
+    polygon: &mut Polygon,
+    cfg: &Config,
+    p: Vec2,
+    r0: Vec2,
+    r1: Vec2,
+    h: f32,
+    cap: LineCap,
+) {
+    let d = (r0 - r1).perp().normalize();
+    let r = right(d);
+
+    match cap {
+        LineCap::Butt => {}
+        LineCap::Square => {
+            polygon.push(p + (r + d) * h);
+            polygon.push(p + (d - r) * h);
         }
+        LineCap::Round => {
+            // split in two to keep the direction of the arcs unambiguous
+            push_arc(polygon, cfg, p, r0, d, h);
+            polygon.push(p + d * h);
+            push_arc(polygon, cfg, p, d, r1, h);
+        }
+        LineCap::Triangle { length } => {
+            polygon.push(p + d * length);
+        }
+        LineCap::Arrow { width, length } => {
+            polygon.push(p + r * width / 2.0);
+            polygon.push(p + d * length);
+            polygon.push(p - r * width / 2.0);
+        }
+    }
+}
 
-        let mut polygon = Polygon::default();
+/// Pushes the corner at `p` of a side offset to the right by `h`, along the unit normal `n0`
+/// before `p` and `n1` after it, with `join` on the outer side.
+///
+/// Inner corners are cut at the intersection of the offset edges if it's within `max_overlap`
+/// of their ends, otherwise they are connected through `p`, such that regions where the side
+/// folds over itself end up with a non positive winding.
+pub(super) fn push_join(
+    polygon: &mut Polygon,
+    cfg: &Config,
+    p: Vec2,
+    (n0, n1): (Vec2, Vec2),
+    h: f32,
+    join: LineJoin,
+    max_overlap: f32,
+) {
+    let turn = n0.perp_dot(n1);
+    let n = (n0 + n1).normalize_or_zero();
+
+    // straight
+    if turn.abs() < f32::EPSILON && n0.dot(n1) > 0.0 {
+        polygon.push(p + n0 * h);
+        return;
+    }
 
-        // starting cap
-        let p0 = line.points[0];
-        let p1 = line.points[1];
+    // distance to the intersection of the offset edges in units of h
+    let miter = 1.0 / n0.dot(n);
 
-        let d = p1 - p0;
-        let a = d.y.atan2(d.x) + PI / 2.0;
+    if turn < 0.0 {
+        // inner corner, distance from the ends of the offset edges to their intersection
+        let overlap = h * n0.perp_dot(n).abs() * miter;
 
-        if self.round {
-            push_cap(&mut polygon, cfg, p0, self.thickness / 2.0, a);
+        if n != Vec2::ZERO && overlap <= max_overlap {
+            polygon.push(p + n * h * miter);
         } else {
-            let p0 = line.points[0];
-            let p1 = line.points[1];
+            polygon.push(p + n0 * h);
+            polygon.push(p);
+            polygon.push(p + n1 * h);
+        }
 
-            let n = nor(p1 - p0).normalize();
+        return;
+    }
 
-            polygon.points.push(p0 - n * self.thickness / 2.0);
+    match join {
+        LineJoin::Miter { limit } if n != Vec2::ZERO && miter <= limit => {
+            polygon.push(p + n * h * miter);
+        }
+        LineJoin::Miter { .. } | LineJoin::Bevel => {
+            polygon.push(p + n0 * h);
+            polygon.push(p + n1 * h);
         }
+        LineJoin::Round => {
+            polygon.push(p + n0 * h);
+            push_arc(polygon, cfg, p, n0, n1, h);
+            polygon.push(p + n1 * h);
+        }
+    }
+}
 
-        for i in 1..line.points.len() - 1 {
-            let p0 = line.points[i - 1];
-            let p1 = line.points[i];
-            let p2 = line.points[i + 1];
+impl<T> Thicken<T> {
+    /// Pushes the right side of `points`, with joins on the outer corners.
+    fn push_side(&self, polygon: &mut Polygon, cfg: &Config, points: &[Vec2]) {
+        let h = self.thickness / 2.0;
 
-            let n0 = nor((p1 - p0).normalize());
-            let n1 = nor((p2 - p1).normalize());
+        polygon.push(points[0] + right((points[1] - points[0]).normalize()) * h);
 
-            let n = (n0 + n1).normalize();
+        for i in 1..points.len() - 1 {
+            let (p0, p1, p2) = (points[i - 1], points[i], points[i + 1]);
 
-            let p = p1 - n * self.thickness / 2.0;
+            let r0 = right((p1 - p0).normalize());
+            let r1 = right((p2 - p1).normalize());
 
-            polygon.push(p);
+            // the offset edges are as long as the segments
+            let max_overlap = p0.distance(p1).min(p1.distance(p2));
+
+            push_join(polygon, cfg, p1, (r0, r1), h, self.join, max_overlap);
         }
 
-        // ending cap
-        let p0 = line.points[line.points.len() - 1];
-        let p1 = line.points[line.points.len() - 2];
+        let last = points.len() - 1;
+        polygon.push(points[last] + right((points[last] - points[last - 1]).normalize()) * h);
+    }
+
+    /// Pushes the cap at the end of `points`, excluding the points on the sides.
+    #[inline]
+    fn push_cap(&self, polygon: &mut Polygon, cfg: &Config, points: &[Vec2], cap: LineCap) {
+        let p0 = points[points.len() - 2];
+        let p1 = points[points.len() - 1];
 
-        let d = p1 - p0;
-        let a = d.y.atan2(d.x) + PI / 2.0;
+        let r = right((p1 - p0).normalize());
 
-        if self.round {
-            push_cap(&mut polygon, cfg, p0, self.thickness / 2.0, a);
-        } else {
-            let p0 = line.points[line.points.len() - 1];
-            let p1 = line.points[line.points.len() - 2];
+        push_cap(polygon, cfg, p1, r, -r, self.thickness / 2.0, cap);
+    }
+
+    /// Returns an empty polygon if the line has no points, or a single point and butt caps.
+    fn thicken(&self, cfg: &Config, mut line: Polyline) -> Result<Polygon, Error> {
+        crate::error::finite(&line.points)?;
+
+        line.points.dedup();
 
-            let n = nor(p1 - p0).normalize();
+        let mut polygon = Polygon::default();
 
-            polygon.points.push(p0 + n * self.thickness / 2.0);
-            polygon.points.push(p0 - n * self.thickness / 2.0);
+        if line.points.is_empty() {
+            return Ok(polygon);
         }
 
-        for i in (1..line.points.len() - 1).rev() {
-            let p0 = line.points[i - 1];
-            let p1 = line.points[i];
-            let p2 = line.points[i + 1];
+        if line.points.len() == 1 {
+            let p = line.points[0];
+            let d = match line.direction.map(Vec2::normalize_or_zero) {
+                Some(d) if d != Vec2::ZERO => d,
+                _ => Vec2::X,
+            };
+            let r = right(d) * self.thickness / 2.0;
+
+            // the caps of a line without length, facing away from each other
+            polygon.push(p + r);
+            self.push_cap(&mut polygon, cfg, &[p - d, p], self.end_cap);
+            polygon.push(p - r);
+            self.push_cap(&mut polygon, cfg, &[p + d, p], self.start_cap);
+
+            if polygon.points.len() < 3 {
+                polygon.points.clear();
+            }
+
+            return Ok(polygon);
+        }
 
-            let n0 = nor((p1 - p0).normalize());
-            let n1 = nor((p2 - p1).normalize());
+        // walk down the right side and back up the left
+        self.push_side(&mut polygon, cfg, &line.points);
+        self.push_cap(&mut polygon, cfg, &line.points, self.end_cap);
 
-            let n = (n0 + n1).normalize();
+        line.points.reverse();
 
-            let p = p1 + n * self.thickness / 2.0;
+        self.push_side(&mut polygon, cfg, &line.points);
+        self.push_cap(&mut polygon, cfg, &line.points, self.start_cap);
 
-            polygon.push(p);
+        // sharp inner corners fold over and the line may overlap itself
+        if !self.overlaps {
+            polygon.remove_intersection();
+            polygon.make_simple();
         }
 
-        if !self.round {
-            let p0 = line.points[0];
-            let p1 = line.points[1];
+        Ok(polygon)
+    }
+}
 
-            let n = nor(p1 - p0).normalize();
+impl Shape for Thicken<Polyline> {
+    type Input = Polyline;
+    type Output = Polygon;
 
-            polygon.points.push(p0 + n * self.thickness / 2.0);
+    #[inline]
+    fn generate(&self, cfg: &Config, line: Self::Input) -> Self::Output {
+        self.try_generate(cfg, line)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Returns an empty polygon if the line has no points, or a single point and butt caps.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, line: Self::Input) -> Result<Self::Output, Error> {
+        self.thicken(cfg, line)
+    }
+}
+
+impl Shape for Thicken<Vec<Polyline>> {
+    type Input = Vec<Polyline>;
+    type Output = Vec<Polygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, lines: Self::Input) -> Self::Output {
+        self.try_generate(cfg, lines)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Lines thickened into empty polygons are left out.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, lines: Self::Input) -> Result<Self::Output, Error> {
+        let mut polygons = Vec::with_capacity(lines.len());
+
+        for line in lines {
+            let polygon = self.thicken(cfg, line)?;
+
+            if !polygon.points.is_empty() {
+                polygons.push(polygon);
+            }
         }
 
-        polygon.remove_intersection();
-        polygon.make_simple();
+        Ok(polygons)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mesh::Mesh,
+        shapes::{Fill, FillRule},
+    };
+
+    fn area(mesh: &Mesh) -> f32 {
+        mesh.indices
+            .chunks_exact(3)
+            .map(|t| {
+                let p = |i: u32| mesh.vertices[i as usize].position.truncate();
+
+                (p(t[1]) - p(t[0])).perp_dot(p(t[2]) - p(t[0])).abs() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn caps() {
+        let cfg = Config::new(0.001);
+        let line = Polyline::from(vec![Vec2::ZERO, Vec2::new(2.0, 0.0)]);
+
+        let area = |cap| {
+            Thicken::<Polyline>::new(0.2)
+                .cap(cap)
+                .generate(&cfg, line.clone())
+                .area()
+                .abs()
+        };
+
+        let arrow = LineCap::Arrow {
+            width: 0.6,
+            length: 0.3,
+        };
+
+        assert!((area(LineCap::Butt) - 0.4).abs() < 1e-4);
+        assert!((area(LineCap::Square) - 2.2 * 0.2).abs() < 1e-4);
+        assert!((area(LineCap::Round) - (0.4 + std::f32::consts::PI * 0.01)).abs() < 1e-4);
+        assert!((area(LineCap::Triangle { length: 0.3 }) - (0.4 + 0.2 * 0.3)).abs() < 1e-4);
+        assert!((area(arrow) - (0.4 + 0.6 * 0.3)).abs() < 1e-4);
+
+        // different caps on both ends
+        let polygon = Thicken::<Polyline>::new(0.2)
+            .start_cap(LineCap::Square)
+            .end_cap(LineCap::Triangle { length: 0.3 })
+            .generate(&cfg, line);
+        let (min, max) = polygon.bounds();
+
+        assert!((min.x + 0.1).abs() < 1e-6 && (max.x - 2.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn overlaps() {
+        let cfg = Config::default();
+
+        // back along the line, overlapping half of it
+        let line = Polyline::from(vec![
+            Vec2::ZERO,
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 0.1),
+            Vec2::new(0.0, 0.1),
+        ]);
+
+        let polygon = Thicken::<Polyline>::new(0.2)
+            .overlaps()
+            .generate(&cfg, line);
+
+        let mesh = Fill::<Polygon, _>::new([1.0; 4])
+            .fill_rule(FillRule::NonZero)
+            .generate(&cfg, polygon);
 
-        polygon
+        // the miter joins square off the corners
+        assert!((area(&mesh) - 2.1 * 0.3).abs() < 1e-4, "{}", area(&mesh));
     }
 }
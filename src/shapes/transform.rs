@@ -0,0 +1,274 @@
+use glam::Affine2;
+
+use crate::{
+    error::Error, holed_polygon::HoledPolygon, mesh::Mesh, polygon::Polygon, polyline::Polyline,
+    Config, Shape,
+};
+
+/// Transforms geometry by an [`Affine2`].
+///
+/// Unlike [`MapPolygon`](super::MapPolygon) this keeps all guarantees about polygons, so no
+/// re-verification is needed.
+#[derive(Clone, Debug)]
+pub struct Transform<T> {
+    pub affine: Affine2,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> Transform<T> {
+    #[inline]
+    pub const fn new(affine: Affine2) -> Self {
+        Self {
+            affine,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Checks that the affine is finite.
+    #[inline]
+    fn check(&self) -> Result<(), Error> {
+        if self.affine.is_finite() {
+            Ok(())
+        } else {
+            Err(Error::NonFinite)
+        }
+    }
+}
+
+impl Shape for Transform<Polygon> {
+    type Input = Polygon;
+    type Output = Polygon;
+
+    #[inline]
+    fn generate(&self, _cfg: &Config, mut polygon: Self::Input) -> Self::Output {
+        polygon.transform(self.affine);
+        polygon
+    }
+
+    /// Returns an error if the affine or the transformed coordinates aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polygon: Self::Input) -> Result<Self::Output, Error> {
+        self.check()?;
+
+        let polygon = self.generate(cfg, polygon);
+
+        crate::error::finite(&polygon.points)?;
+
+        Ok(polygon)
+    }
+}
+
+impl Shape for Transform<Vec<Polygon>> {
+    type Input = Vec<Polygon>;
+    type Output = Vec<Polygon>;
+
+    #[inline]
+    fn generate(&self, _cfg: &Config, mut polygons: Self::Input) -> Self::Output {
+        for polygon in &mut polygons {
+            polygon.transform(self.affine);
+        }
+
+        polygons
+    }
+
+    /// Returns an error if the affine or the transformed coordinates aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polygons: Self::Input) -> Result<Self::Output, Error> {
+        self.check()?;
+
+        let polygons = self.generate(cfg, polygons);
+
+        for polygon in &polygons {
+            crate::error::finite(&polygon.points)?;
+        }
+
+        Ok(polygons)
+    }
+}
+
+impl Shape for Transform<HoledPolygon> {
+    type Input = HoledPolygon;
+    type Output = HoledPolygon;
+
+    #[inline]
+    fn generate(&self, _cfg: &Config, mut polygon: Self::Input) -> Self::Output {
+        polygon.transform(self.affine);
+        polygon
+    }
+
+    /// Returns an error if the affine or the transformed coordinates aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polygon: Self::Input) -> Result<Self::Output, Error> {
+        self.check()?;
+
+        let polygon = self.generate(cfg, polygon);
+
+        crate::error::finite(&polygon.polygon.points)?;
+
+        for hole in &polygon.holes {
+            crate::error::finite(&hole.points)?;
+        }
+
+        Ok(polygon)
+    }
+}
+
+impl Shape for Transform<Vec<HoledPolygon>> {
+    type Input = Vec<HoledPolygon>;
+    type Output = Vec<HoledPolygon>;
+
+    #[inline]
+    fn generate(&self, _cfg: &Config, mut polygons: Self::Input) -> Self::Output {
+        for polygon in &mut polygons {
+            polygon.transform(self.affine);
+        }
+
+        polygons
+    }
+
+    /// Returns an error if the affine or the transformed coordinates aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polygons: Self::Input) -> Result<Self::Output, Error> {
+        self.check()?;
+
+        let polygons = self.generate(cfg, polygons);
+
+        for polygon in &polygons {
+            crate::error::finite(&polygon.polygon.points)?;
+
+            for hole in &polygon.holes {
+                crate::error::finite(&hole.points)?;
+            }
+        }
+
+        Ok(polygons)
+    }
+}
+
+impl Shape for Transform<Polyline> {
+    type Input = Polyline;
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, _cfg: &Config, mut polyline: Self::Input) -> Self::Output {
+        polyline.transform(self.affine);
+        polyline
+    }
+
+    /// Returns an error if the affine or the transformed coordinates aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polyline: Self::Input) -> Result<Self::Output, Error> {
+        self.check()?;
+
+        let polyline = self.generate(cfg, polyline);
+
+        crate::error::finite(&polyline.points)?;
+
+        Ok(polyline)
+    }
+}
+
+impl Shape for Transform<Vec<Polyline>> {
+    type Input = Vec<Polyline>;
+    type Output = Vec<Polyline>;
+
+    #[inline]
+    fn generate(&self, _cfg: &Config, mut polylines: Self::Input) -> Self::Output {
+        for polyline in &mut polylines {
+            polyline.transform(self.affine);
+        }
+
+        polylines
+    }
+
+    /// Returns an error if the affine or the transformed coordinates aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, polylines: Self::Input) -> Result<Self::Output, Error> {
+        self.check()?;
+
+        let polylines = self.generate(cfg, polylines);
+
+        for polyline in &polylines {
+            crate::error::finite(&polyline.points)?;
+        }
+
+        Ok(polylines)
+    }
+}
+
+impl Shape for Transform<Mesh> {
+    type Input = Mesh;
+    type Output = Mesh;
+
+    #[inline]
+    fn generate(&self, _cfg: &Config, mut mesh: Self::Input) -> Self::Output {
+        mesh.transform(self.affine);
+        mesh
+    }
+
+    /// Returns an error if the affine or the transformed coordinates aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, mesh: Self::Input) -> Result<Self::Output, Error> {
+        self.check()?;
+
+        let mesh = self.generate(cfg, mesh);
+
+        if !mesh.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec2;
+
+    use super::*;
+
+    #[test]
+    fn non_finite() {
+        let cfg = Config::default();
+        let polygon = Polygon::from(vec![Vec2::ZERO, Vec2::X * 2.0, Vec2::Y * 2.0]);
+
+        let scale =
+            |factor: f32| Transform::<Polygon>::new(Affine2::from_scale(Vec2::splat(factor)));
+
+        assert!(scale(2.0).try_generate(&cfg, polygon.clone()).is_ok());
+        let result = scale(f32::NAN).try_generate(&cfg, polygon.clone());
+        assert!(matches!(result, Err(Error::NonFinite)));
+
+        // overflowing coordinates
+        let result = scale(f32::MAX).try_generate(&cfg, polygon);
+        assert!(matches!(result, Err(Error::NonFinite)));
+    }
+
+    #[test]
+    fn singular() {
+        let cfg = Config::default();
+
+        let mut line = Polyline::from(vec![Vec2::ZERO, Vec2::X]);
+        line.direction = Some(Vec2::X);
+
+        // collapsing the direction unsets it rather than making it NaN
+        let collapse = Transform::<Polyline>::new(Affine2::from_scale(Vec2::new(0.0, 1.0)));
+        assert_eq!(collapse.generate(&cfg, line.clone()).direction, None);
+
+        let stretch = Transform::<Polyline>::new(Affine2::from_scale(Vec2::new(2.0, 1.0)));
+        assert_eq!(stretch.generate(&cfg, line).direction, Some(Vec2::X));
+    }
+
+    #[test]
+    fn polygons() {
+        let cfg = Config::default();
+        let polygon = Polygon::from(vec![Vec2::ZERO, Vec2::X, Vec2::Y]);
+
+        let translate = Transform::<Vec<Polygon>>::new(Affine2::from_translation(Vec2::ONE));
+        let polygons = translate.generate(&cfg, vec![polygon.clone(), polygon]);
+
+        assert!(polygons
+            .iter()
+            .all(|polygon| polygon.points[0] == Vec2::ONE));
+    }
+}
@@ -0,0 +1,161 @@
+use glam::Vec2;
+
+use crate::{
+    error::Error, path_measure::PathMeasure, polygon::Polygon, polyline::Polyline, Config, Shape,
+};
+
+/// Line along `measure` from distance `start` to `end`, see [`PathMeasure::extract`], with the
+/// direction set to the tangent at the end.
+pub(super) fn extract(measure: &PathMeasure, start: f32, end: f32) -> Polyline {
+    let points = measure.extract(start, end);
+
+    let direction = if let [.., p0, p1] = points[..] {
+        Some((p1 - p0).normalize())
+    } else {
+        // nothing is left, the tangent of the path keeps the line extendable
+        Some(measure.tangent(end.max(start))).filter(|tangent| *tangent != Vec2::ZERO)
+    };
+
+    Polyline { points, direction }
+}
+
+/// Keeps the part of a path from `start` to `end`, as fractions of its length where 0 is the
+/// start and 1 the end, see [`PathMeasure::extract`].
+///
+/// Lines clamp the range to the path. The outline of a polygon is trimmed into a line, measured
+/// from its first point, and a range past the first point or with `end` before `start` wraps
+/// around it.
+///
+/// The direction of the resulting polyline is the tangent at the new end, useful for animating
+/// a line drawing itself before [`Thicken`](super::Thicken).
+#[derive(Clone, Debug)]
+pub struct Trim<T> {
+    pub start: f32,
+    pub end: f32,
+    _marker: std::marker::PhantomData<*const T>,
+}
+
+impl<T> Trim<T> {
+    #[inline]
+    pub const fn new(start: f32, end: f32) -> Self {
+        Self {
+            start,
+            end,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Trims the path through `points`, `direction` is kept if the range reaches the end.
+    fn trim(
+        &self,
+        points: &[Vec2],
+        closed: bool,
+        direction: Option<Vec2>,
+    ) -> Result<Polyline, Error> {
+        if !self.start.is_finite() || !self.end.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        crate::error::finite(points)?;
+
+        if points.is_empty() {
+            return Err(Error::Degenerate(
+                "trimmed path must have at least one point",
+            ));
+        }
+
+        let measure = PathMeasure::new(points, closed);
+        let length = measure.length();
+        let mut line = extract(&measure, self.start * length, self.end * length);
+
+        if !closed && self.end >= 1.0 {
+            line.direction = direction;
+        }
+
+        Ok(line)
+    }
+}
+
+impl Shape for Trim<Polyline> {
+    type Input = Polyline;
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.trim(&input.points, false, input.direction)
+    }
+}
+
+impl Shape for Trim<Polygon> {
+    type Input = Polygon;
+    type Output = Polyline;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, input: Self::Input) -> Self::Output {
+        self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[inline]
+    fn try_generate(&self, _cfg: &Config, input: Self::Input) -> Result<Self::Output, Error> {
+        self.trim(&input.points, true, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2::ZERO,
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ]
+    }
+
+    #[test]
+    fn line() {
+        let cfg = Config::default();
+        let line = Polyline {
+            points: square(),
+            direction: Some(-Vec2::X),
+        };
+
+        let trimmed = Trim::<Polyline>::new(0.0, 0.5).generate(&cfg, line.clone());
+        assert_eq!(trimmed.points.last(), Some(&Vec2::new(1.0, 0.5)));
+        assert_eq!(trimmed.direction, Some(Vec2::Y));
+
+        // reaching the end keeps the direction
+        let trimmed = Trim::<Polyline>::new(0.5, 2.0).generate(&cfg, line.clone());
+        assert_eq!(trimmed.points[0], Vec2::new(1.0, 0.5));
+        assert_eq!(trimmed.direction, Some(-Vec2::X));
+
+        // nothing is left of a reversed range
+        let trimmed = Trim::<Polyline>::new(0.5, 0.25).generate(&cfg, line);
+        assert_eq!(trimmed.points, vec![Vec2::new(1.0, 0.5)]);
+        assert_eq!(trimmed.direction, Some(Vec2::Y));
+    }
+
+    #[test]
+    fn polygon_wraps() {
+        let cfg = Config::default();
+        let polygon = Polygon::from(square());
+
+        let expected = vec![Vec2::new(0.0, 0.5), Vec2::ZERO, Vec2::new(0.5, 0.0)];
+
+        // past the first point
+        let trimmed = Trim::<Polygon>::new(0.875, 1.125).generate(&cfg, polygon.clone());
+        assert_eq!(trimmed.points, expected);
+
+        // end before start
+        let trimmed = Trim::<Polygon>::new(0.875, 0.125).generate(&cfg, polygon);
+        assert_eq!(trimmed.points, expected);
+        assert_eq!(trimmed.direction, Some(Vec2::X));
+    }
+}
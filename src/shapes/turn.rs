@@ -1,6 +1,6 @@
 use glam::Mat2;
 
-use crate::{Polyline, Shape};
+use crate::{Error, Polyline, Shape};
 
 #[derive(Clone, Debug)]
 pub struct Turn {
@@ -13,17 +13,23 @@ impl Shape for Turn {
 	type Output = Polyline;
 
 	#[inline]
-	fn generate(&self, cfg: &crate::Config, mut input: Self::Input) -> Self::Output {
-		let dist = self.angle.abs() * self.radius;
-		let steps = (dist / cfg.resolution).ceil() as usize;
+	fn generate(&self, cfg: &crate::Config, input: Self::Input) -> Self::Output {
+		self.try_generate(cfg, input).unwrap_or_else(|err| panic!("{}", err))
+	}
+
+	#[inline]
+	fn try_generate(&self, cfg: &crate::Config, mut input: Self::Input) -> Result<Self::Output, Error> {
+		let direction = input.try_direction()?;
+		let mut p = *input.points.last().ok_or(Error::Degenerate("polyline has no points"))?;
+
+		let steps = cfg.arc_steps(self.radius, self.angle);
 
 		let angle_per_step = self.angle / steps as f32;
 		let dist_per_step = (angle_per_step.abs() / 2.0).sin() * self.radius * 2.0;
 
 		let rot = Mat2::from_angle(angle_per_step);
 
-		let mut p = input.points[input.points.len() - 1];
-		let mut vec = (p - input.points[input.points.len() - 2]).normalize() * dist_per_step;
+		let mut vec = direction * dist_per_step;
 
 		for _ in 0..steps {
 			vec = rot * vec;
@@ -32,8 +38,8 @@ impl Shape for Turn {
 			input.push(p);
 		}
 
-		input.direction = Some(vec.normalize());
+		input.direction = Some(Mat2::from_angle(self.angle) * direction);
 
-		input
+		Ok(input)
 	}	
 }
\ No newline at end of file
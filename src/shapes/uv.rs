@@ -0,0 +1,225 @@
+use glam::Vec2;
+
+use crate::{error::Error, mesh::Mesh, polyline::Polyline, Config, Shape};
+
+/// How texture coordinates are mapped onto a [`Mesh`] by [`Uv`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UvMapping {
+    /// Maps the bounding box of the mesh to `0..1`.
+    Planar,
+    /// Uses the position multiplied by `scale`, such that a texture repeats every `1 / scale`.
+    World { scale: Vec2 },
+}
+
+/// Sets the texture coordinates of a [`Mesh`].
+#[derive(Clone, Debug)]
+pub struct Uv {
+    pub mapping: UvMapping,
+}
+
+impl Shape for Uv {
+    type Input = Mesh;
+    type Output = Mesh;
+
+    #[inline]
+    fn generate(&self, _cfg: &Config, mut mesh: Self::Input) -> Self::Output {
+        match self.mapping {
+            UvMapping::Planar => {
+                let mut min = Vec2::splat(f32::INFINITY);
+                let mut max = Vec2::splat(f32::NEG_INFINITY);
+
+                for vertex in &mesh.vertices {
+                    min = min.min(vertex.position.truncate());
+                    max = max.max(vertex.position.truncate());
+                }
+
+                let size = (max - min).max(Vec2::splat(f32::EPSILON));
+
+                for vertex in &mut mesh.vertices {
+                    vertex.uv = (vertex.position.truncate() - min) / size;
+                }
+            }
+            UvMapping::World { scale } => {
+                for vertex in &mut mesh.vertices {
+                    vertex.uv = vertex.position.truncate() * scale;
+                }
+            }
+        }
+
+        mesh
+    }
+
+    /// Returns an error if the positions or the scale aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, mesh: Self::Input) -> Result<Self::Output, Error> {
+        if !mesh.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        if let UvMapping::World { scale } = self.mapping {
+            if !scale.is_finite() {
+                return Err(Error::NonFinite);
+            }
+        }
+
+        Ok(self.generate(cfg, mesh))
+    }
+}
+
+/// Maps texture coordinates along a line onto the mesh of the thickened line.
+///
+/// `u` is the distance along the line and `v` goes from 0 on the right side to 1 on the left.
+/// Takes the mesh and line as input, which can be gotten using [`split`](crate::ShapeExt::split).
+///
+/// # Example
+/// ```
+/// # use scissor::prelude::*;
+/// let road = Line::new((0.0, 0.0), (4.0, 0.0))
+///     .turn(2.0, 1.0)
+///     .split(
+///         |line| line.thicken(0.5, false).fill([1.0; 4]),
+///         |line| line,
+///     )
+///     .stroke_uv(0.5);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StrokeUv {
+    pub thickness: f32,
+}
+
+impl Shape for StrokeUv {
+    type Input = (Mesh, Polyline);
+    type Output = Mesh;
+
+    fn generate(&self, _cfg: &Config, (mut mesh, line): Self::Input) -> Self::Output {
+        let segments = line.points.len().saturating_sub(1);
+
+        for vertex in &mut mesh.vertices {
+            let p = vertex.position.truncate();
+
+            let mut closest = f32::INFINITY;
+            let mut distance = 0.0;
+
+            for i in 0..segments {
+                let p0 = line.points[i];
+                let p1 = line.points[i + 1];
+
+                let d = p1 - p0;
+                let length = d.length();
+
+                if length == 0.0 {
+                    continue;
+                }
+
+                let mut t = (p - p0).dot(d) / (length * length);
+
+                // caps extend past the ends of the line
+                if i > 0 {
+                    t = t.max(0.0);
+                }
+
+                if i + 1 < segments {
+                    t = t.min(1.0);
+                }
+
+                let q = p0 + d * t;
+                let dist = p.distance(q);
+
+                if dist < closest {
+                    closest = dist;
+
+                    let side = d.perp_dot(p - p0).signum() * dist;
+
+                    vertex.uv = Vec2::new(distance + t * length, 0.5 + side / self.thickness);
+                }
+
+                distance += length;
+            }
+        }
+
+        mesh
+    }
+
+    /// Returns an error if the thickness isn't positive or the positions aren't finite.
+    #[inline]
+    fn try_generate(&self, cfg: &Config, (mesh, line): Self::Input) -> Result<Self::Output, Error> {
+        crate::error::positive(self.thickness, "thickness must be positive")?;
+        crate::error::finite(&line.points)?;
+
+        if !mesh.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        Ok(self.generate(cfg, (mesh, line)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        polygon::Polygon,
+        shapes::{Fill, LineCap, Rect, Thicken},
+    };
+
+    fn mesh(polygon: Polygon) -> Mesh {
+        Fill::<Polygon, _>::new([1.0; 4]).generate(&Config::default(), polygon)
+    }
+
+    #[test]
+    fn planar() {
+        let cfg = Config::default();
+        let rect = mesh(Rect::new(4.0, 2.0).generate(&cfg, ()));
+
+        let planar = Uv {
+            mapping: UvMapping::Planar,
+        }
+        .generate(&cfg, rect.clone());
+
+        for vertex in &planar.vertices {
+            // corners of the rect map to the corners of the texture
+            assert!(vertex.uv.x == 0.0 || vertex.uv.x == 1.0);
+            assert!(vertex.uv.y == 0.0 || vertex.uv.y == 1.0);
+        }
+
+        let world = Uv {
+            mapping: UvMapping::World {
+                scale: Vec2::new(0.5, 2.0),
+            },
+        }
+        .generate(&cfg, rect);
+
+        for vertex in &world.vertices {
+            assert_eq!(vertex.uv, vertex.position.truncate() * Vec2::new(0.5, 2.0));
+        }
+    }
+
+    #[test]
+    fn stroke() {
+        let cfg = Config::default();
+
+        // turning left at (2, 0)
+        let line = Polyline::from(vec![Vec2::ZERO, Vec2::new(2.0, 0.0), Vec2::new(2.0, 2.0)]);
+        let polygon = Thicken::<Polyline>::new(0.5)
+            .cap(LineCap::Butt)
+            .generate(&cfg, line.clone());
+
+        let mesh = StrokeUv { thickness: 0.5 }.generate(&cfg, (mesh(polygon), line));
+
+        let uv = |position: Vec2| {
+            let vertex = mesh
+                .vertices
+                .iter()
+                .find(|v| v.position.truncate().distance(position) < 1e-6)
+                .unwrap();
+
+            vertex.uv
+        };
+
+        // u along the line, v from the right side to the left
+        assert_eq!(uv(Vec2::new(0.0, -0.25)), Vec2::new(0.0, 0.0));
+        assert_eq!(uv(Vec2::new(0.0, 0.25)), Vec2::new(0.0, 1.0));
+        assert_eq!(uv(Vec2::new(2.25, 2.0)), Vec2::new(4.0, 0.0));
+        assert_eq!(uv(Vec2::new(1.75, 2.0)), Vec2::new(4.0, 1.0));
+    }
+}
@@ -0,0 +1,422 @@
+use glam::Vec2;
+
+use crate::{
+    boolean, error::Error, holed_polygon::HoledPolygon, polygon::Polygon, polyline::Polyline,
+    Config, Shape,
+};
+
+use super::{
+    thicken::{push_cap, push_join, right},
+    LineCap, LineJoin,
+};
+
+/// Largest number of samples a line is subdivided into.
+const MAX_SAMPLES: usize = 1 << 20;
+
+/// Determines the width along a line thickened by [`VariableThicken`].
+///
+/// Implemented for constant widths, widths per point `Vec<f32>` and closures `Fn(f32) -> f32`
+/// taking the distance along the line from 0 at the start to 1 at the end.
+pub trait Width {
+    /// Width at point `index` of the line, which lies at `t` along its length.
+    fn width(&self, index: usize, t: f32) -> f32;
+
+    /// Whether the width changes between points, in which case segments are subdivided.
+    #[inline]
+    fn is_continuous(&self) -> bool {
+        false
+    }
+}
+
+impl Width for f32 {
+    #[inline]
+    fn width(&self, _index: usize, _t: f32) -> f32 {
+        *self
+    }
+}
+
+impl Width for Vec<f32> {
+    /// The last width is repeated for points past the end.
+    #[inline]
+    fn width(&self, index: usize, _t: f32) -> f32 {
+        self.get(index)
+            .or_else(|| self.last())
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
+
+impl<F: Fn(f32) -> f32> Width for F {
+    #[inline]
+    fn width(&self, _index: usize, t: f32) -> f32 {
+        self(t)
+    }
+
+    #[inline]
+    fn is_continuous(&self) -> bool {
+        true
+    }
+}
+
+/// Thickens a [`Polyline`] with a [`Width`] varying along it, such as pen pressure.
+///
+/// The sides are tangent to circles of the width around every point, giving smooth transitions
+/// between widths. The ends can be tapered to a point over a distance, and segments are
+/// subdivided by [`Config::resolution`] where the width changes between points.
+///
+/// Overlapping parts are merged, which is why the result may consist of any number of polygons.
+/// Lines with less than two distinct points result in none.
+///
+/// # Example
+/// ```
+/// # use scissor::prelude::*;
+/// let brush = Line::new((0.0, 0.0), (4.0, 0.0))
+///     .stroke_variable(
+///         VariableThicken::new(|t: f32| 0.2 + t * 0.3)
+///             .taper(1.0, 0.5)
+///             .cap(LineCap::Round),
+///     )
+///     .fill([1.0; 4]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct VariableThicken<W> {
+    pub width: W,
+    pub join: LineJoin,
+    pub start_cap: LineCap,
+    pub end_cap: LineCap,
+    /// Distance from the start over which the width grows from zero.
+    pub start_taper: f32,
+    /// Distance from the end over which the width shrinks to zero.
+    pub end_taper: f32,
+}
+
+impl<W: Width> VariableThicken<W> {
+    #[inline]
+    pub fn new(width: W) -> Self {
+        Self {
+            width,
+            join: LineJoin::Round,
+            start_cap: LineCap::Round,
+            end_cap: LineCap::Round,
+            start_taper: 0.0,
+            end_taper: 0.0,
+        }
+    }
+
+    /// Sets the [`LineCap`] of both ends.
+    #[inline]
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.start_cap = cap;
+        self.end_cap = cap;
+        self
+    }
+
+    /// Sets the [`LineCap`] at the start of the line.
+    #[inline]
+    pub fn start_cap(mut self, cap: LineCap) -> Self {
+        self.start_cap = cap;
+        self
+    }
+
+    /// Sets the [`LineCap`] at the end of the line.
+    #[inline]
+    pub fn end_cap(mut self, cap: LineCap) -> Self {
+        self.end_cap = cap;
+        self
+    }
+
+    /// Sets the [`LineJoin`].
+    #[inline]
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Sets the distances over which the start and end taper to a point.
+    #[inline]
+    pub fn taper(mut self, start: f32, end: f32) -> Self {
+        self.start_taper = start;
+        self.end_taper = end;
+        self
+    }
+
+    /// Factor of the width at `distance` along a line of `length`, easing out of the tapers.
+    #[inline]
+    fn taper_factor(&self, distance: f32, length: f32) -> f32 {
+        let ease = |x: f32| x * (2.0 - x);
+
+        let mut factor = 1.0;
+
+        if self.start_taper > 0.0 {
+            factor *= ease((distance / self.start_taper).min(1.0));
+        }
+
+        if self.end_taper > 0.0 {
+            factor *= ease(((length - distance) / self.end_taper).min(1.0));
+        }
+
+        factor
+    }
+
+    /// `points` with their half widths, subdivided where the width changes.
+    fn samples(&self, cfg: &Config, points: &[Vec2]) -> Result<Vec<(Vec2, f32)>, Error> {
+        let mut distances = Vec::with_capacity(points.len());
+        let mut distance = 0.0;
+
+        for i in 0..points.len() {
+            if i > 0 {
+                distance += points[i - 1].distance(points[i]);
+            }
+
+            distances.push(distance);
+        }
+
+        let length = distance;
+        let t = |distance: f32| if length > 0.0 { distance / length } else { 0.0 };
+
+        // spaces the subdivisions further apart on lines too long for the resolution
+        let resolution = cfg.resolution.max(length / MAX_SAMPLES as f32);
+
+        let mut samples: Vec<(Vec2, f32)> = Vec::with_capacity(points.len());
+        let mut previous = 0.0;
+
+        for (i, (&p, &distance)) in points.iter().zip(&distances).enumerate() {
+            let width = self.width.width(i, t(distance));
+
+            if !width.is_finite() {
+                return Err(Error::NonFinite);
+            }
+
+            if i > 0 {
+                let d0 = distances[i - 1];
+                let tapered = d0 < self.start_taper || distance > length - self.end_taper;
+
+                let steps = if self.width.is_continuous() || tapered {
+                    ((distance - d0) / resolution).ceil() as usize
+                } else {
+                    1
+                };
+
+                for step in 1..steps {
+                    let s = step as f32 / steps as f32;
+                    let d = d0 + (distance - d0) * s;
+
+                    let width = if self.width.is_continuous() {
+                        self.width.width(i - 1, t(d))
+                    } else {
+                        previous + (width - previous) * s
+                    };
+
+                    let h = width.max(0.0) / 2.0 * self.taper_factor(d, length);
+                    samples.push((points[i - 1].lerp(p, s), h));
+                }
+            }
+
+            let h = width.max(0.0) / 2.0 * self.taper_factor(distance, length);
+
+            match samples.last_mut() {
+                // points without distance between them keep the widest
+                Some((last, last_h)) if *last == p => *last_h = last_h.max(h),
+                _ => samples.push((p, h)),
+            }
+
+            previous = width;
+        }
+
+        Ok(samples)
+    }
+
+    /// Normals of the sides of every segment, tangent to the circles at both ends.
+    fn normals(samples: &[(Vec2, f32)]) -> Vec<Vec2> {
+        samples
+            .windows(2)
+            .map(|w| {
+                let ((p0, h0), (p1, h1)) = (w[0], w[1]);
+
+                let d = (p1 - p0).normalize();
+
+                // angle the side is tilted by, where one circle doesn't contain the other
+                let sin = ((h1 - h0) / p0.distance(p1)).clamp(-1.0, 1.0);
+                let cos = (1.0 - sin * sin).sqrt();
+
+                right(d) * cos - d * sin
+            })
+            .collect()
+    }
+
+    /// Pushes the right side of `samples`, with joins on the outer corners, see [`push_join`].
+    fn push_side(
+        &self,
+        polygon: &mut Polygon,
+        cfg: &Config,
+        samples: &[(Vec2, f32)],
+        normals: &[Vec2],
+    ) {
+        // length of the side along segment `i`
+        let side = |i: usize| {
+            let ((p0, h0), (p1, h1)) = (samples[i], samples[i + 1]);
+
+            (p0 + normals[i] * h0).distance(p1 + normals[i] * h1)
+        };
+
+        let (p, h) = samples[0];
+        polygon.push(p + normals[0] * h);
+
+        for i in 1..samples.len() - 1 {
+            let (p, h) = samples[i];
+            let normals = (normals[i - 1], normals[i]);
+
+            push_join(
+                polygon,
+                cfg,
+                p,
+                normals,
+                h,
+                self.join,
+                side(i - 1).min(side(i)),
+            );
+        }
+
+        let (p, h) = samples[samples.len() - 1];
+        polygon.push(p + normals[normals.len() - 1] * h);
+    }
+}
+
+impl<W: Width> Shape for VariableThicken<W> {
+    type Input = Polyline;
+    type Output = Vec<HoledPolygon>;
+
+    #[inline]
+    fn generate(&self, cfg: &Config, line: Self::Input) -> Self::Output {
+        self.try_generate(cfg, line)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_generate(&self, cfg: &Config, line: Self::Input) -> Result<Self::Output, Error> {
+        crate::error::finite(&line.points)?;
+
+        if !self.start_taper.is_finite() || !self.end_taper.is_finite() {
+            return Err(Error::NonFinite);
+        }
+
+        let mut samples = self.samples(cfg, &line.points)?;
+
+        if samples.len() < 2 {
+            return Ok(Vec::new());
+        }
+
+        let normals = Self::normals(&samples);
+
+        let mut polygon = Polygon::with_capacity(samples.len() * 2);
+
+        // walk down the right side and back up the left
+        self.push_side(&mut polygon, cfg, &samples, &normals);
+
+        samples.reverse();
+        let reversed = Self::normals(&samples);
+
+        let (end, end_h) = samples[0];
+        let (start, start_h) = samples[samples.len() - 1];
+
+        let (first, last) = (normals[0], normals[normals.len() - 1]);
+        let (reversed_first, reversed_last) = (reversed[0], reversed[reversed.len() - 1]);
+
+        push_cap(
+            &mut polygon,
+            cfg,
+            end,
+            last,
+            reversed_first,
+            end_h,
+            self.end_cap,
+        );
+        self.push_side(&mut polygon, cfg, &samples, &reversed);
+        push_cap(
+            &mut polygon,
+            cfg,
+            start,
+            reversed_last,
+            first,
+            start_h,
+            self.start_cap,
+        );
+
+        // parts of the sides folding over themselves or crossing each other are merged
+        let rings = [polygon.points.as_slice()];
+
+        Ok(boolean::resolve(&[rings.to_vec()], |windings| {
+            windings[0] > 0
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(polygons: &[HoledPolygon]) -> f32 {
+        polygons
+            .iter()
+            .map(|p| p.polygon.area() - p.holes.iter().map(Polygon::area).sum::<f32>())
+            .sum()
+    }
+
+    fn line() -> Polyline {
+        Polyline::from(vec![Vec2::ZERO, Vec2::new(4.0, 0.0)])
+    }
+
+    #[test]
+    fn constant() {
+        let cfg = Config::default();
+
+        let polygons = VariableThicken::new(1.0)
+            .cap(LineCap::Butt)
+            .generate(&cfg, line());
+
+        assert_eq!(polygons.len(), 1);
+        assert!((area(&polygons) - 4.0).abs() < 1e-4, "{}", area(&polygons));
+    }
+
+    #[test]
+    fn per_point() {
+        let cfg = Config::default();
+
+        let polygons = VariableThicken::new(vec![1.0, 3.0])
+            .cap(LineCap::Butt)
+            .generate(&cfg, line());
+
+        // the sides are tangent to both circles, tilting the ends back by the change in radius
+        let sin: f32 = (1.5 - 0.5) / 4.0;
+        let cos = (1.0 - sin * sin).sqrt();
+        let expected = (4.0 - sin * (1.5 - 0.5)) * (0.5 + 1.5) * cos;
+
+        assert!(
+            (area(&polygons) - expected).abs() < 1e-3,
+            "{}",
+            area(&polygons)
+        );
+    }
+
+    #[test]
+    fn tapers() {
+        let cfg = Config::new(0.01);
+
+        let polygons = VariableThicken::new(1.0)
+            .cap(LineCap::Butt)
+            .taper(1.0, 1.0)
+            .generate(&cfg, line());
+
+        let points = &polygons[0].polygon.points;
+
+        // ends in points, full width in between
+        assert!(points.contains(&Vec2::ZERO));
+        assert!(points.contains(&Vec2::new(4.0, 0.0)));
+        assert!(points.iter().any(|p| (p.y - 0.5).abs() < 1e-4));
+
+        // easing out of the tapers covers two thirds of their width, the sides tangent to the
+        // circles a bit more
+        let area = area(&polygons);
+
+        assert!(area > 2.0 + 2.0 * 2.0 / 3.0 && area < 3.5, "{}", area);
+    }
+}
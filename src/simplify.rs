@@ -0,0 +1,496 @@
+//! Topology preserving simplification of lines and rings.
+//!
+//! Both algorithms only ever replace part of a chain by a straight segment if the area between
+//! the two contains no other points, which guarantees that no new intersections are introduced,
+//! neither within a chain nor between chains.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use glam::Vec2;
+
+use crate::polygon::{self, Polygon};
+
+/// Points of either an open line or a closed ring.
+#[derive(Clone, Copy)]
+pub(crate) struct Chain<'a> {
+    pub points: &'a [Vec2],
+    pub closed: bool,
+}
+
+impl Chain<'_> {
+    /// Minimum number of points left after simplification.
+    #[inline]
+    fn min_len(&self) -> usize {
+        if self.closed {
+            3
+        } else {
+            2
+        }
+    }
+}
+
+/// Checks if segment `a0` to `a1` and `b0` to `b1` cross, not counting touching.
+#[inline]
+fn crosses(a0: Vec2, a1: Vec2, b0: Vec2, b1: Vec2) -> bool {
+    let d0 = (a1 - a0).perp_dot(b0 - a0);
+    let d1 = (a1 - a0).perp_dot(b1 - a0);
+    let d2 = (b1 - b0).perp_dot(a0 - b0);
+    let d3 = (b1 - b0).perp_dot(a1 - b0);
+
+    d0 * d1 < 0.0 && d2 * d3 < 0.0
+}
+
+#[inline]
+fn outside_bounds((min, max): (Vec2, Vec2), p: Vec2) -> bool {
+    p.cmplt(min).any() || p.cmpgt(max).any()
+}
+
+/// Uniform grid of the points of every chain, such that only points near a region are tested.
+struct Grid {
+    min: Vec2,
+    cell_size: f32,
+    width: usize,
+    height: usize,
+    cells: Vec<Vec<(usize, usize)>>,
+}
+
+impl Grid {
+    fn new(chains: &[Chain]) -> Self {
+        let (min, max) = chains.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), chain| {
+                let (chain_min, chain_max) = polygon::bounds(chain.points);
+
+                (min.min(chain_min), max.max(chain_max))
+            },
+        );
+
+        let len: usize = chains.iter().map(|chain| chain.points.len()).sum();
+        let extent = (max - min).max_element();
+
+        // roughly one point per cell
+        let cell_size = if extent > 0.0 {
+            extent / (len as f32).sqrt().max(1.0)
+        } else {
+            1.0
+        };
+
+        let width = ((max.x - min.x) / cell_size) as usize + 1;
+        let height = ((max.y - min.y) / cell_size) as usize + 1;
+
+        let mut grid = Self {
+            min,
+            cell_size,
+            width,
+            height,
+            cells: vec![Vec::new(); width * height],
+        };
+
+        for (c, chain) in chains.iter().enumerate() {
+            for (i, &p) in chain.points.iter().enumerate() {
+                let (x, y) = grid.cell(p);
+
+                grid.cells[y * width + x].push((c, i));
+            }
+        }
+
+        grid
+    }
+
+    #[inline]
+    fn cell(&self, p: Vec2) -> (usize, usize) {
+        let cell = (p - self.min) / self.cell_size;
+
+        (
+            (cell.x.max(0.0) as usize).min(self.width - 1),
+            (cell.y.max(0.0) as usize).min(self.height - 1),
+        )
+    }
+
+    /// Checks if `f` returns true for any point, of chain and index, within `bounds`.
+    #[inline]
+    fn any(&self, (min, max): (Vec2, Vec2), mut f: impl FnMut(usize, usize) -> bool) -> bool {
+        let (x0, y0) = self.cell(min);
+        let (x1, y1) = self.cell(max);
+
+        (y0..=y1).any(|y| {
+            (x0..=x1).any(|x| self.cells[y * self.width + x].iter().any(|&(c, i)| f(c, i)))
+        })
+    }
+}
+
+/// Checks if `i` lies in `start..=end` of a chain of length `len`, where `end` may wrap past
+/// the end of a ring.
+#[inline]
+fn in_range(i: usize, start: usize, end: usize, len: usize) -> bool {
+    let i = if i < start { i + len } else { i };
+
+    i <= end
+}
+
+/// Checks if the points `start..=end` of chain `index` can be replaced by a single segment.
+fn can_replace(chains: &[Chain], grid: &Grid, index: usize, start: usize, end: usize) -> bool {
+    let chain = chains[index];
+    let len = chain.points.len();
+
+    let region = Polygon::from(
+        (start..=end)
+            .map(|i| chain.points[i % len])
+            .collect::<Vec<_>>(),
+    );
+
+    let a = region.points[0];
+    let b = region.points[region.points.len() - 1];
+
+    // the region between the segment and the chain must be simple for the point test below
+    if region.points.windows(2).any(|w| crosses(a, b, w[0], w[1])) {
+        return false;
+    }
+
+    let bounds = region.bounds();
+
+    !grid.any(bounds, |other, i| {
+        let p = chains[other].points[i];
+
+        if other == index && in_range(i, start, end, len) || p == a || p == b {
+            return false;
+        }
+
+        if outside_bounds(bounds, p) {
+            return false;
+        }
+
+        let on_segment = (b - a).perp_dot(p - a) == 0.0 && !outside_bounds((a.min(b), a.max(b)), p);
+
+        on_segment || region.contains(p)
+    })
+}
+
+/// Simplifies every chain, keeping all points further than `tolerance` from the result.
+pub(crate) fn douglas_peucker(chains: &[Chain], tolerance: f32) -> Vec<Vec<Vec2>> {
+    let grid = Grid::new(chains);
+
+    chains
+        .iter()
+        .enumerate()
+        .map(|(index, chain)| {
+            let points = chain.points;
+            let len = points.len();
+
+            if len <= chain.min_len() {
+                return points.to_vec();
+            }
+
+            let at = |i: usize| points[i % len];
+
+            let farthest = |start: usize, end: usize, distance: &dyn Fn(Vec2) -> f32| {
+                (start + 1..end)
+                    .map(|i| (i, distance(at(i))))
+                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+                    .unwrap()
+            };
+
+            let mut keep = vec![false; len];
+            let mut anchors = vec![0, len - 1];
+
+            // rings are anchored in a triangle to ensure they never collapse
+            if chain.closed {
+                let (far, _) = farthest(0, len, &|p| p.distance(at(0)));
+                let (third, _) = farthest(0, len, &|p| {
+                    if p == at(far) {
+                        0.0
+                    } else {
+                        p.distance(polygon::closest_point_on_segment(at(0), at(far), p))
+                    }
+                });
+
+                anchors = vec![0, far.min(third), far.max(third), len];
+            }
+
+            let mut stack = Vec::new();
+
+            for w in anchors.windows(2) {
+                keep[w[0] % len] = true;
+                keep[w[1] % len] = true;
+
+                stack.push((w[0], w[1]));
+            }
+
+            while let Some((start, end)) = stack.pop() {
+                if end - start < 2 {
+                    continue;
+                }
+
+                let (a, b) = (at(start), at(end));
+
+                let (i, distance) = farthest(start, end, &|p| {
+                    p.distance(polygon::closest_point_on_segment(a, b, p))
+                });
+
+                if distance <= tolerance && can_replace(chains, &grid, index, start, end) {
+                    continue;
+                }
+
+                keep[i] = true;
+
+                stack.push((start, i));
+                stack.push((i, end));
+            }
+
+            (0..len).filter(|&i| keep[i]).map(at).collect()
+        })
+        .collect()
+}
+
+/// Point queued for removal by [`visvalingam_whyatt`], ordered by smallest area first.
+struct Candidate {
+    area: f32,
+    chain: usize,
+    index: usize,
+    version: u32,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Simplifies every chain, removing points until all remaining form triangles of at least
+/// `tolerance` area with their neighbours.
+pub(crate) fn visvalingam_whyatt(chains: &[Chain], tolerance: f32) -> Vec<Vec<Vec2>> {
+    #[inline]
+    fn area(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+        (b - a).perp_dot(c - a).abs() / 2.0
+    }
+
+    let mut prev: Vec<Vec<usize>> = Vec::with_capacity(chains.len());
+    let mut next: Vec<Vec<usize>> = Vec::with_capacity(chains.len());
+    let mut removed: Vec<Vec<bool>> = Vec::with_capacity(chains.len());
+    let mut versions: Vec<Vec<u32>> = Vec::with_capacity(chains.len());
+    let mut lens: Vec<usize> = Vec::with_capacity(chains.len());
+
+    let grid = Grid::new(chains);
+    let mut queue = BinaryHeap::new();
+
+    // blocked candidates by the point blocking them, queued again once it's removed
+    let mut waiting: HashMap<(usize, usize), Vec<Candidate>> = HashMap::new();
+
+    for (index, chain) in chains.iter().enumerate() {
+        let len = chain.points.len();
+
+        prev.push((0..len).map(|i| (i + len - 1) % len).collect());
+        next.push((0..len).map(|i| (i + 1) % len).collect());
+        removed.push(vec![false; len]);
+        versions.push(vec![0; len]);
+        lens.push(len);
+
+        if len <= chain.min_len() {
+            continue;
+        }
+
+        // the end points of lines are never removed
+        let range = if chain.closed { 0..len } else { 1..len - 1 };
+
+        for i in range {
+            queue.push(Candidate {
+                area: area(
+                    chain.points[prev[index][i]],
+                    chain.points[i],
+                    chain.points[next[index][i]],
+                ),
+                chain: index,
+                index: i,
+                version: 0,
+            });
+        }
+    }
+
+    while let Some(candidate) = queue.pop() {
+        if candidate.area >= tolerance {
+            break;
+        }
+
+        let (c, i) = (candidate.chain, candidate.index);
+
+        if removed[c][i] || versions[c][i] != candidate.version {
+            continue;
+        }
+
+        if lens[c] <= chains[c].min_len() {
+            continue;
+        }
+
+        let (p, n) = (prev[c][i], next[c][i]);
+        let triangle = [
+            chains[c].points[p],
+            chains[c].points[i],
+            chains[c].points[n],
+        ];
+
+        let bounds = polygon::bounds(&triangle);
+        let mut blocker = None;
+
+        // removing the point is only safe if no other point lies within the triangle, since
+        // any segment crossing the new segment must end inside it
+        let blocked = grid.any(bounds, |other, j| {
+            if removed[other][j] || other == c && (j == p || j == i || j == n) {
+                return false;
+            }
+
+            let q = chains[other].points[j];
+
+            if outside_bounds(bounds, q) {
+                return false;
+            }
+
+            let d0 = (triangle[1] - triangle[0]).perp_dot(q - triangle[0]);
+            let d1 = (triangle[2] - triangle[1]).perp_dot(q - triangle[1]);
+            let d2 = (triangle[0] - triangle[2]).perp_dot(q - triangle[2]);
+
+            let inside =
+                (d0 >= 0.0 && d1 >= 0.0 && d2 >= 0.0) || (d0 <= 0.0 && d1 <= 0.0 && d2 <= 0.0);
+
+            if inside {
+                blocker = Some((other, j));
+            }
+
+            inside
+        });
+
+        if blocked {
+            if let Some(blocker) = blocker {
+                waiting.entry(blocker).or_default().push(candidate);
+            }
+
+            continue;
+        }
+
+        removed[c][i] = true;
+
+        if let Some(unblocked) = waiting.remove(&(c, i)) {
+            queue.extend(unblocked);
+        }
+        next[c][p] = n;
+        prev[c][n] = p;
+        lens[c] -= 1;
+
+        for j in [p, n] {
+            if !chains[c].closed && (j == 0 || j == chains[c].points.len() - 1) {
+                continue;
+            }
+
+            versions[c][j] += 1;
+
+            let points = chains[c].points;
+
+            queue.push(Candidate {
+                // effective area never decreases, such that neighbours of removed points
+                // aren't removed prematurely
+                area: area(points[prev[c][j]], points[j], points[next[c][j]]).max(candidate.area),
+                chain: c,
+                index: j,
+                version: versions[c][j],
+            });
+        }
+    }
+
+    chains
+        .iter()
+        .enumerate()
+        .map(|(c, chain)| {
+            (0..chain.points.len())
+                .filter(|&i| !removed[c][i])
+                .map(|i| chain.points[i])
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unblocked_after_removal() {
+        // the middle point of `a` is removed first, but is blocked by the tip of `b` until
+        // that is removed as well
+        let a = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.02),
+            Vec2::new(2.0, 0.0),
+        ];
+        let b = [
+            Vec2::new(0.9, -1.0),
+            Vec2::new(1.0, 0.01),
+            Vec2::new(1.1, -1.0),
+        ];
+
+        let chains = [
+            Chain {
+                points: &a,
+                closed: false,
+            },
+            Chain {
+                points: &b,
+                closed: false,
+            },
+        ];
+
+        let simplified = visvalingam_whyatt(&chains, 0.2);
+
+        assert_eq!(simplified[0], vec![a[0], a[2]]);
+        assert_eq!(simplified[1], vec![b[0], b[2]]);
+    }
+
+    #[test]
+    fn blocked() {
+        // the tip of `b` is too large to be removed, so `a` has to keep its middle point
+        let a = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.02),
+            Vec2::new(2.0, 0.0),
+        ];
+        let b = [
+            Vec2::new(0.5, -1.0),
+            Vec2::new(1.0, 0.01),
+            Vec2::new(1.5, -1.0),
+        ];
+
+        let chains = [
+            Chain {
+                points: &a,
+                closed: false,
+            },
+            Chain {
+                points: &b,
+                closed: false,
+            },
+        ];
+
+        let simplified = visvalingam_whyatt(&chains, 0.2);
+
+        assert_eq!(simplified[0], a.to_vec());
+        assert_eq!(simplified[1], b.to_vec());
+    }
+}
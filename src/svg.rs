@@ -0,0 +1,292 @@
+//! Export of geometry to svg documents, useful for inspecting what a shape generates.
+//!
+//! # Example
+//! ```
+//! # use scissor::{prelude::*, svg::Document, Config};
+//! let cfg = Config::default();
+//!
+//! let mut document = Document::new();
+//! document.wireframe = true;
+//! document.add(&Circle::new(4.0).fill([1.0, 0.0, 0.0, 1.0]).generate(&cfg, ()));
+//!
+//! let svg = document.to_string();
+//! ```
+//!
+//! The y axis is flipped such that the output matches the coordinate system used by the crate.
+
+use std::{fmt, io, path::Path};
+
+use glam::Vec2;
+
+use crate::{holed_polygon::HoledPolygon, mesh::Mesh, polygon::Polygon, polyline::Polyline};
+
+/// Geometry that can be written to a [`Document`].
+pub trait ToSvg {
+    fn write_svg(&self, document: &mut Document);
+}
+
+/// An svg document.
+///
+/// The style fields apply to geometry added after they're set.
+#[derive(Clone, Debug)]
+pub struct Document {
+    /// Fill color of polygons.
+    pub fill: [f32; 4],
+    /// Stroke color of polygon outlines, polylines and mesh wireframes.
+    pub stroke: [f32; 4],
+    /// Stroke width in pixels.
+    pub stroke_width: f32,
+    /// Draws the edges of mesh triangles.
+    pub wireframe: bool,
+    /// Labels mesh vertices with their index.
+    pub labels: bool,
+    elements: Vec<String>,
+    labels_at: Vec<(Vec2, usize)>,
+    min: Vec2,
+    max: Vec2,
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Self {
+            fill: [0.5, 0.5, 0.5, 1.0],
+            stroke: [0.0, 0.0, 0.0, 1.0],
+            stroke_width: 1.0,
+            wireframe: false,
+            labels: false,
+            elements: Vec::new(),
+            labels_at: Vec::new(),
+            min: Vec2::splat(f32::INFINITY),
+            max: Vec2::splat(f32::NEG_INFINITY),
+        }
+    }
+}
+
+/// Formats a color as an svg paint and opacity.
+#[inline]
+fn paint(color: [f32; 4]) -> (String, f32) {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    (
+        format!(
+            "rgb({},{},{})",
+            channel(color[0]),
+            channel(color[1]),
+            channel(color[2])
+        ),
+        color[3].clamp(0.0, 1.0),
+    )
+}
+
+impl Document {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds geometry to the document.
+    #[inline]
+    pub fn add(&mut self, geometry: &impl ToSvg) -> &mut Self {
+        geometry.write_svg(self);
+        self
+    }
+
+    /// Writes the document to a file.
+    #[inline]
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    /// Formats a point, flipping the y axis.
+    #[inline]
+    fn point(&mut self, p: Vec2) -> String {
+        self.min = self.min.min(p);
+        self.max = self.max.max(p);
+
+        format!("{},{}", p.x, -p.y)
+    }
+
+    #[inline]
+    fn points(&mut self, points: &[Vec2]) -> String {
+        let points: Vec<String> = points.iter().map(|p| self.point(*p)).collect();
+
+        points.join(" ")
+    }
+
+    #[inline]
+    fn fill_attributes(&self) -> String {
+        let (fill, fill_opacity) = paint(self.fill);
+
+        format!(r#"fill="{}" fill-opacity="{}""#, fill, fill_opacity)
+    }
+
+    #[inline]
+    fn stroke_attributes(&self) -> String {
+        let (stroke, stroke_opacity) = paint(self.stroke);
+
+        format!(
+            r#"stroke="{}" stroke-opacity="{}" stroke-width="{}" stroke-linejoin="round" vector-effect="non-scaling-stroke""#,
+            stroke, stroke_opacity, self.stroke_width
+        )
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min, max) = if self.min.x <= self.max.x {
+            (self.min, self.max)
+        } else {
+            (Vec2::ZERO, Vec2::ONE)
+        };
+
+        let size = (max - min).max(Vec2::splat(f32::EPSILON));
+        let padding = size.max_element() * 0.05;
+
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+            min.x - padding,
+            -max.y - padding,
+            size.x + padding * 2.0,
+            size.y + padding * 2.0,
+            512.0,
+            512.0 * (size.y + padding * 2.0) / (size.x + padding * 2.0),
+        )?;
+
+        for element in &self.elements {
+            writeln!(f, "  {}", element)?;
+        }
+
+        let font_size = size.max_element() * 0.02;
+
+        for (p, i) in &self.labels_at {
+            writeln!(
+                f,
+                r#"  <text x="{}" y="{}" font-size="{}" font-family="monospace">{}</text>"#,
+                p.x, -p.y, font_size, i
+            )?;
+        }
+
+        writeln!(f, "</svg>")
+    }
+}
+
+impl ToSvg for Polygon {
+    #[inline]
+    fn write_svg(&self, document: &mut Document) {
+        let element = format!(
+            r#"<polygon points="{}" {} {}/>"#,
+            document.points(&self.points),
+            document.fill_attributes(),
+            document.stroke_attributes(),
+        );
+
+        document.elements.push(element);
+    }
+}
+
+impl ToSvg for HoledPolygon {
+    #[inline]
+    fn write_svg(&self, document: &mut Document) {
+        let mut d = String::new();
+
+        for polygon in std::iter::once(&self.polygon).chain(&self.holes) {
+            d += &format!("M{}Z", document.points(&polygon.points));
+        }
+
+        let element = format!(
+            r#"<path d="{}" fill-rule="evenodd" {} {}/>"#,
+            d,
+            document.fill_attributes(),
+            document.stroke_attributes(),
+        );
+
+        document.elements.push(element);
+    }
+}
+
+impl ToSvg for Polyline {
+    #[inline]
+    fn write_svg(&self, document: &mut Document) {
+        let element = format!(
+            r#"<polyline points="{}" fill="none" {}/>"#,
+            document.points(&self.points),
+            document.stroke_attributes(),
+        );
+
+        document.elements.push(element);
+    }
+}
+
+/// Svg has no per vertex colors, so each triangle is filled with the average of its vertices.
+impl ToSvg for Mesh {
+    fn write_svg(&self, document: &mut Document) {
+        for triangle in self.indices.chunks_exact(3) {
+            let vertices = [
+                self.vertices[triangle[0] as usize],
+                self.vertices[triangle[1] as usize],
+                self.vertices[triangle[2] as usize],
+            ];
+
+            let mut color = [0.0; 4];
+
+            for vertex in &vertices {
+                for (c, v) in color.iter_mut().zip(vertex.color.iter()) {
+                    *c += v / 3.0;
+                }
+            }
+
+            let points: Vec<Vec2> = vertices.iter().map(|v| v.position.truncate()).collect();
+            let (fill, fill_opacity) = paint(color);
+
+            let stroke = if document.wireframe {
+                document.stroke_attributes()
+            } else {
+                // hides the seams between triangles
+                format!(
+                    r#"stroke="{}" stroke-opacity="{}" stroke-width="0.5" vector-effect="non-scaling-stroke""#,
+                    fill, fill_opacity
+                )
+            };
+
+            let element = format!(
+                r#"<polygon points="{}" fill="{}" fill-opacity="{}" {}/>"#,
+                document.points(&points),
+                fill,
+                fill_opacity,
+                stroke,
+            );
+
+            document.elements.push(element);
+        }
+
+        if document.labels {
+            for (i, vertex) in self.vertices.iter().enumerate() {
+                document.labels_at.push((vertex.position.truncate(), i));
+            }
+        }
+    }
+}
+
+impl<T: ToSvg> ToSvg for Vec<T> {
+    #[inline]
+    fn write_svg(&self, document: &mut Document) {
+        for geometry in self {
+            geometry.write_svg(document);
+        }
+    }
+}
+
+impl<T: ToSvg, U: ToSvg> ToSvg for (T, U) {
+    #[inline]
+    fn write_svg(&self, document: &mut Document) {
+        self.0.write_svg(document);
+        self.1.write_svg(document);
+    }
+}
+
+/// Creates an svg document containing `geometry` with the default style.
+#[inline]
+pub fn to_svg(geometry: &impl ToSvg) -> String {
+    Document::new().add(geometry).to_string()
+}